@@ -1,4 +1,25 @@
-use crate::{Num, Op, Value};
+use crate::{Op, Position, Value};
+
+/// How an argument is passed to a subroutine parameter
+#[derive(Clone, Debug, PartialEq)]
+pub enum Passing {
+    /// No mode was given: arrays are passed by reference, everything else by value
+    Default,
+    ByVal,
+    ByRef,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub ident: String,
+    pub passing: Passing,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Visibility {
+    Public,
+    Private,
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Node {
@@ -6,42 +27,138 @@ pub enum Node {
     Assign {
         ident: String,
         value: Box<Node>,
+        pos: Position,
     },
+    /// `array board[3, 3]`, with one size per dimension
     ArrayAssign {
         ident: String,
-        size: Box<Node>,
+        sizes: Vec<Node>,
+        pos: Position,
+    },
+    /// `array names = ["a", "b"]`, with the size taken from the literal
+    ArrayLiteralAssign {
+        ident: String,
+        elements: Vec<Node>,
     },
     ArrayAssingIndex {
         ident: String,
-        index: Box<Node>,
+        indices: Vec<Node>,
         value: Box<Node>,
+        pos: Position,
     },
     IfExpr {
         expr: Box<Node>,
         then: Box<Node>,
+        /// Each `elseif` condition and body, with the position of the `elseif`
+        elseifs: Vec<(Node, Node, Position)>,
         els: Box<Node>,
+        pos: Position,
+    },
+    Switch {
+        expr: Box<Node>,
+        cases: Vec<(Node, Node)>,
+        default: Option<Box<Node>>,
     },
     WhileStmt {
         expr: Box<Node>,
         body: Box<Node>,
+        pos: Position,
+    },
+    /// `pos` is the position of `until`, next to the condition
+    DoUntil {
+        body: Box<Node>,
+        expr: Box<Node>,
+        pos: Position,
+    },
+    ForStmt {
+        ident: String,
+        start: Box<Node>,
+        end: Box<Node>,
+        step: Option<Box<Node>>,
+        body: Box<Node>,
+        pos: Position,
     },
     FuncCall {
         ident: String,
         args: Vec<Node>,
+        pos: Position,
+    },
+    FunctionDecl {
+        ident: String,
+        params: Vec<Param>,
+        body: Box<Node>,
+    },
+    ProcedureDecl {
+        ident: String,
+        params: Vec<Param>,
+        body: Box<Node>,
+    },
+    /// `value` is `None` for a bare `return` in a procedure
+    Return {
+        value: Option<Box<Node>>,
+        pos: Position,
+    },
+    /// Methods are stored as `FunctionDecl` or `ProcedureDecl` nodes.
+    /// `parent` is the superclass named after `inherits`, with its position.
+    ClassDecl {
+        ident: String,
+        parent: Option<(String, Position)>,
+        attributes: Vec<(String, Visibility)>,
+        methods: Vec<(Node, Visibility)>,
+    },
+    /// `new Pet(...)`, creating an object and calling its constructor
+    New {
+        class: String,
+        args: Vec<Node>,
+        pos: Position,
+    },
+    /// `super.method(...)`, calling the superclass's version of a method
+    SuperCall {
+        method: String,
+        args: Vec<Node>,
+        pos: Position,
+    },
+    /// `object.attribute = value`
+    AttributeAssign {
+        object: Box<Node>,
+        ident: String,
+        value: Box<Node>,
+        pos: Position,
+    },
+    /// `global x = ...`, declaring a variable visible in every subroutine
+    GlobalDecl {
+        ident: String,
+        value: Box<Node>,
+    },
+    VariableRef {
+        ident: String,
+        pos: Position,
     },
-    VariableRef(String),
     ArrayRef {
         ident: String,
-        index: Box<Node>,
+        indices: Vec<Node>,
+        pos: Position,
+    },
+    UnaryExpr {
+        operator: Op,
+        expr: Box<Node>,
+        pos: Position,
     },
     BinaryExpr {
         left: Box<Node>,
         operator: Op,
         right: Box<Node>,
+        pos: Position,
     },
+    /// `[1, 2, 3]`, creating an array with one element per expression
+    ArrayLiteral(Vec<Node>),
+    /// A property (`s.length`) or method call (`s.substring(0, 3)`) on a value.
+    /// `args` is `None` when no brackets follow the member name.
     DotExpr {
-        left: String,
+        left: Box<Node>,
         right: String,
+        args: Option<Vec<Node>>,
+        pos: Position,
     },
     Primary(Value),
 }
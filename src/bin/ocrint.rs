@@ -1,4 +1,4 @@
-use std::fs;
+use std::{fs, path::PathBuf};
 
 use clap::Parser as CParser;
 use ocr_language::{interpretor::Interpretor, lexer::Lexer, parser::Parser};
@@ -13,6 +13,14 @@ struct Cli {
     #[arg(short, long)]
     debug: bool,
 
+    /// The directory that files opened by the program are relative to
+    #[arg(short = 'C', long, default_value = ".")]
+    working_dir: PathBuf,
+
+    /// Seed for `random`, so that programs using it give the same output every run
+    #[arg(long)]
+    seed: Option<u64>,
+
     /// The program that should be run
     program: String,
 }
@@ -61,6 +69,12 @@ pub fn main() {
         println!("Running program:");
     }
 
-    let mut interpretor = Interpretor::new(Box::new(ast));
-    interpretor.run();
+    let mut interpretor = Interpretor::new(Box::new(ast), input).with_working_dir(cli.working_dir);
+    if let Some(seed) = cli.seed {
+        interpretor = interpretor.with_seed(seed);
+    }
+    if let Err(e) = interpretor.run() {
+        println!("Error while running:");
+        println!("{}", e);
+    }
 }
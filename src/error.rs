@@ -1,11 +1,13 @@
 use core::fmt;
 use std::{error::Error, fmt::Display};
 
-use crate::{lexer::Token, Position};
+use crate::{lexer::Token, Num, Position, Value};
 
 #[derive(Clone, Debug)]
 pub enum LexerError {
     UnrecognisedCharacter(char, Position, String),
+    UnterminatedComment(Position, String),
+    NumberTooLarge(String, Position, String),
 }
 
 impl Error for LexerError {}
@@ -17,13 +19,27 @@ impl Display for LexerError {
                 let _ = writeln!(f, "Unrecognised Character");
                 write_position(f, p, 1, i)
             }
+            Self::UnterminatedComment(p, i) => {
+                let _ = writeln!(f, "Block comment is never closed with `*/`");
+                write_position(f, p, 2, i)
+            }
+            Self::NumberTooLarge(number, p, i) => {
+                let _ = writeln!(f, "`{}` is too large to be an integer", number);
+                write_position(f, p, number.len(), i)
+            }
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub enum ParserError {
-    InvalidTokenInBlock(Token, String),
+    InvalidTokenInBlock(Box<Token>, String),
+    MismatchedNextVariable(Box<Token>, String, String),
+    InvalidTokenInSwitch(Box<Token>, String),
+    InvalidTokenInClass(Box<Token>, String),
+    InvalidAssignTarget(Box<Token>, String),
+    IncompleteStatement(Box<Token>, String),
+    ExpectedArrayLiteral(Box<Token>, String),
 }
 
 impl Error for ParserError {}
@@ -35,16 +51,304 @@ impl Display for ParserError {
                 let _ = writeln!(f, "Invalid statement at the root of block");
                 write_position(f, &t.start, t.len, input)
             }
-            _ => todo!(),
+            Self::MismatchedNextVariable(t, ident, input) => {
+                let _ = writeln!(f, "Expected `next {}` to close the for loop", ident);
+                write_position(f, &t.start, t.len, input)
+            }
+            Self::InvalidTokenInSwitch(t, input) => {
+                let _ = writeln!(f, "Expected `case`, `default` or `endswitch` in switch");
+                write_position(f, &t.start, t.len, input)
+            }
+            Self::InvalidTokenInClass(t, input) => {
+                let _ = writeln!(f, "Expected an attribute, method or `endclass` in class");
+                write_position(f, &t.start, t.len, input)
+            }
+            Self::InvalidAssignTarget(t, input) => {
+                let _ = writeln!(
+                    f,
+                    "Only variables, array elements and attributes can be assigned to"
+                );
+                write_position(f, &t.start, t.len, input)
+            }
+            Self::IncompleteStatement(t, input) => {
+                let _ = writeln!(f, "Expected `=` or a method call to complete the statement");
+                write_position(f, &t.start, t.len, input)
+            }
+            Self::ExpectedArrayLiteral(t, input) => {
+                let _ = writeln!(f, "Expected an array literal such as `[1, 2, 3]` after `=`");
+                write_position(f, &t.start, t.len, input)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum RuntimeError {
+    Overflow(Position, String),
+    DivisionByZero(Position, String),
+    UnknownMember(Position, String, &'static str, String),
+    UnknownVariable(Position, String, String),
+    InvalidOperand(Position, &'static str, &'static str, &'static str, String),
+    InvalidOperands(Position, &'static str, &'static str, &'static str, String),
+    UnknownSubroutine(Position, String, String),
+    InvalidForValue(Position, &'static str, &'static str, String),
+    ForStepZero(Position, String),
+    MissingReturn(Position, String, String),
+    ProcedureReturn(Position, String, String),
+    NoReturnValue(Position, String, String),
+    ReturnOutsideSubroutine(Position, String),
+    WrongArgumentCount(Position, String, usize, usize, String),
+    TooManyArguments(Position, String, usize, usize, String),
+    InvalidArgument(Position, String, &'static str, &'static str, String),
+    StringIndexOutOfRange(Position, Num, usize, String),
+    ArrayIndexOutOfRange(Position, String, Num, usize, String),
+    InvalidIndex(Position, String, &'static str, String),
+    InvalidArraySize(Position, String, String, String),
+    NotIndexable(Position, &'static str, String),
+    StringIndexAssignment(Position, String),
+    InvalidCharacterCode(Position, Num, String),
+    InvalidConversion(Position, String, String, &'static str, &'static str, String),
+    FileError(Position, String, String, String),
+    InvalidRandomRange(Position, Value, Value, String),
+    ByRefNeedsVariable(Position, String, String, String),
+    UnknownClass(Position, String, String),
+    UnknownClassMember(Position, String, String, String),
+    PrivateMember(Position, String, String, String),
+    UnknownSuperclass(Position, String, String, String),
+    CyclicInheritance(Position, String, String),
+    SuperOutsideMethod(Position, String, String),
+    NoSuperclass(Position, String, String, String),
+}
+
+impl Error for RuntimeError {}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow(p, input) => {
+                let _ = writeln!(f, "Integer overflow");
+                write_position(f, p, 1, input)
+            }
+            Self::DivisionByZero(p, input) => {
+                let _ = writeln!(f, "Division by zero");
+                write_position(f, p, 1, input)
+            }
+            Self::UnknownMember(p, member, type_name, input) => {
+                let _ = writeln!(
+                    f,
+                    "{} values have no property or method `{}`",
+                    type_name, member
+                );
+                write_position(f, p, member.len(), input)
+            }
+            Self::UnknownVariable(p, ident, input) => {
+                let _ = writeln!(f, "Unknown variable `{}`", ident);
+                write_position(f, p, ident.len(), input)
+            }
+            Self::InvalidOperand(p, name, expected, found, input) => {
+                let _ = writeln!(f, "`{}` expects {}, found {}", name, expected, found);
+                write_position(f, p, name.len(), input)
+            }
+            Self::InvalidOperands(p, op, left, right, input) => {
+                let _ = writeln!(f, "`{}` can't be applied to {} and {}", op, left, right);
+                write_position(f, p, op.len(), input)
+            }
+            Self::InvalidForValue(p, part, type_name, input) => {
+                let _ = writeln!(
+                    f,
+                    "The {} of a for loop must be an integer, found {}",
+                    part, type_name
+                );
+                write_position(f, p, 3, input)
+            }
+            Self::ForStepZero(p, input) => {
+                let _ = writeln!(f, "The step of a for loop can't be 0");
+                write_position(f, p, 3, input)
+            }
+            Self::UnknownSubroutine(p, name, input) => {
+                let _ = writeln!(f, "Unknown function or procedure `{}`", name);
+                write_position(f, p, name.len(), input)
+            }
+            Self::MissingReturn(p, name, input) => {
+                let _ = writeln!(f, "Function `{}` ended without returning a value", name);
+                write_position(f, p, name.len(), input)
+            }
+            Self::ProcedureReturn(p, name, input) => {
+                let _ = writeln!(f, "`{}` is a procedure, so it can't return a value", name);
+                write_position(f, p, name.len(), input)
+            }
+            Self::NoReturnValue(p, name, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` doesn't return a value, so it can't be used in an expression",
+                    name
+                );
+                write_position(f, p, name.len(), input)
+            }
+            Self::ReturnOutsideSubroutine(p, input) => {
+                let _ = writeln!(
+                    f,
+                    "`return` can only be used inside a function or procedure"
+                );
+                write_position(f, p, 6, input)
+            }
+            Self::WrongArgumentCount(p, name, expected, found, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` takes {} but {} given",
+                    name,
+                    argument_count(*expected),
+                    given_count(*found)
+                );
+                write_position(f, p, name.len(), input)
+            }
+            Self::TooManyArguments(p, name, max, found, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` takes at most {} but {} given",
+                    name,
+                    argument_count(*max),
+                    given_count(*found)
+                );
+                write_position(f, p, name.len(), input)
+            }
+            Self::InvalidArgument(p, name, expected, found, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` expects an argument of type {}, found {}",
+                    name, expected, found
+                );
+                write_position(f, p, name.len(), input)
+            }
+            Self::StringIndexOutOfRange(p, index, length, input) => {
+                let _ = writeln!(
+                    f,
+                    "Index {} is out of range for a string of length {}",
+                    index, length
+                );
+                write_position(f, p, 1, input)
+            }
+            Self::ArrayIndexOutOfRange(p, ident, index, length, input) => {
+                let _ = writeln!(
+                    f,
+                    "Index {} is out of range for `{}`, which has length {}",
+                    index, ident, length
+                );
+                write_position(f, p, ident.len(), input)
+            }
+            Self::InvalidIndex(p, ident, type_name, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` must be indexed with integers, found {}",
+                    ident, type_name
+                );
+                write_position(f, p, ident.len(), input)
+            }
+            Self::InvalidArraySize(p, ident, size, input) => {
+                let _ = writeln!(
+                    f,
+                    "The size of `{}` must be a positive integer or zero, found {}",
+                    ident, size
+                );
+                write_position(f, p, ident.len(), input)
+            }
+            Self::NotIndexable(p, type_name, input) => {
+                let _ = writeln!(f, "{} values cannot be indexed", type_name);
+                write_position(f, p, 1, input)
+            }
+            Self::StringIndexAssignment(p, input) => {
+                let _ = writeln!(f, "Strings are read-only and cannot be assigned by index");
+                write_position(f, p, 1, input)
+            }
+            Self::InvalidCharacterCode(p, code, input) => {
+                let _ = writeln!(f, "{} is not a valid character code", code);
+                write_position(f, p, 3, input)
+            }
+            Self::InvalidConversion(p, name, value, from, to, input) => {
+                let _ = writeln!(f, "Cannot convert {} {} to {}", from, value, to);
+                write_position(f, p, name.len(), input)
+            }
+            Self::FileError(p, name, message, input) => {
+                let _ = writeln!(f, "`{}` failed: {}", name, message);
+                write_position(f, p, name.len(), input)
+            }
+            Self::InvalidRandomRange(p, min, max, input) => {
+                let _ = writeln!(
+                    f,
+                    "Cannot pick a random number between {} and {}, the minimum is larger than the maximum",
+                    min, max
+                );
+                write_position(f, p, 6, input)
+            }
+            Self::ByRefNeedsVariable(p, ident, param, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` takes `{}` byRef, so it must be given a variable or array element",
+                    ident, param
+                );
+                write_position(f, p, ident.len(), input)
+            }
+            Self::UnknownClass(p, class, input) => {
+                let _ = writeln!(f, "Unknown class `{}`", class);
+                write_position(f, p, 3, input)
+            }
+            Self::UnknownClassMember(p, member, class, input) => {
+                let _ = writeln!(f, "`{}` has no attribute or method `{}`", class, member);
+                write_position(f, p, member.len(), input)
+            }
+            Self::PrivateMember(p, member, class, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` is private, so it can only be used inside `{}`",
+                    member, class
+                );
+                write_position(f, p, member.len(), input)
+            }
+            Self::UnknownSuperclass(p, class, parent, input) => {
+                let _ = writeln!(f, "`{}` inherits from unknown class `{}`", class, parent);
+                write_position(f, p, parent.len(), input)
+            }
+            Self::CyclicInheritance(p, class, input) => {
+                let _ = writeln!(f, "`{}` inherits from itself", class);
+                write_position(f, p, class.len(), input)
+            }
+            Self::SuperOutsideMethod(p, method, input) => {
+                let _ = writeln!(f, "`super.{}` can only be used inside a method", method);
+                write_position(f, p, method.len(), input)
+            }
+            Self::NoSuperclass(p, method, class, input) => {
+                let _ = writeln!(
+                    f,
+                    "`{}` has no superclass, so `super.{}` can't be called",
+                    class, method
+                );
+                write_position(f, p, method.len(), input)
+            }
         }
     }
 }
 
+/// "1 argument", "2 arguments"
+fn argument_count(count: usize) -> String {
+    match count {
+        1 => "1 argument".to_string(),
+        x => format!("{} arguments", x),
+    }
+}
+
+/// "1 was", "2 were"
+fn given_count(count: usize) -> String {
+    match count {
+        1 => "1 was".to_string(),
+        x => format!("{} were", x),
+    }
+}
+
 fn write_position(
     f: &mut fmt::Formatter<'_>,
     position: &Position,
     len: usize,
-    input: &String,
+    input: &str,
 ) -> fmt::Result {
     let (line_num_str, line_num_pad) = line_number_strings(position.line);
     let line = offending_line(position.line, input);
@@ -54,14 +358,13 @@ fn write_position(
     write!(f, "{}{}", line_num_pad, pointer)
 }
 
-fn offending_line(line: usize, input: &String) -> String {
-    let lines = input.clone();
-    lines.lines().nth(line - 1).unwrap().to_string()
+fn offending_line(line: usize, input: &str) -> String {
+    input.lines().nth(line - 1).unwrap().to_string()
 }
 
 fn pointer_string(col: usize, len: usize) -> String {
     let mut padding = String::new();
-    for _ in 1..col {
+    for _ in 0..col {
         padding.push(' ');
     }
     let mut pointer = String::new();
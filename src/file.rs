@@ -0,0 +1,117 @@
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+enum Mode {
+    Read(BufReader<File>),
+    Write(BufWriter<File>),
+    Closed,
+}
+
+/// An open file, as returned by `openRead` and `openWrite`.
+/// Cloning a handle shares the underlying file, so closing one closes them all.
+#[derive(Clone)]
+pub struct FileHandle {
+    path: PathBuf,
+    mode: Rc<RefCell<Mode>>,
+}
+
+impl FileHandle {
+    /// Opens an existing file for reading
+    pub fn open_read(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self::new(path, Mode::Read(BufReader::new(file))))
+    }
+
+    /// Opens a file for writing, replacing its contents if it already exists
+    pub fn open_write(path: &Path) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(path, Mode::Write(BufWriter::new(file))))
+    }
+
+    fn new(path: &Path, mode: Mode) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode: Rc::new(RefCell::new(mode)),
+        }
+    }
+
+    /// Reads the next line, without its line ending. Returns `None` at the end of the file.
+    pub fn read_line(&self) -> Result<Option<String>, String> {
+        let mut mode = self.mode.borrow_mut();
+        let reader = match &mut *mode {
+            Mode::Read(x) => x,
+            Mode::Write(_) => return Err("file was opened for writing".to_string()),
+            Mode::Closed => return Err("file has been closed".to_string()),
+        };
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Ok(Some(line))
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn write_line(&self, line: &str) -> Result<(), String> {
+        let mut mode = self.mode.borrow_mut();
+        let writer = match &mut *mode {
+            Mode::Write(x) => x,
+            Mode::Read(_) => return Err("file was opened for reading".to_string()),
+            Mode::Closed => return Err("file has been closed".to_string()),
+        };
+        writeln!(writer, "{}", line).map_err(|e| e.to_string())
+    }
+
+    pub fn end_of_file(&self) -> Result<bool, String> {
+        let mut mode = self.mode.borrow_mut();
+        let reader = match &mut *mode {
+            Mode::Read(x) => x,
+            Mode::Write(_) => return Err("file was opened for writing".to_string()),
+            Mode::Closed => return Err("file has been closed".to_string()),
+        };
+        reader
+            .fill_buf()
+            .map(|x| x.is_empty())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Closes the file, flushing anything that has been written
+    pub fn close(&self) -> Result<(), String> {
+        let mode = self.mode.replace(Mode::Closed);
+        match mode {
+            Mode::Write(mut x) => x.flush().map_err(|e| e.to_string()),
+            Mode::Read(_) => Ok(()),
+            Mode::Closed => Err("file has already been closed".to_string()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl PartialEq for FileHandle {
+    /// Two handles are only equal if they refer to the same opened file
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.mode, &other.mode)
+    }
+}
+
+impl Debug for FileHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FileHandle({:?})", self.path)
+    }
+}
@@ -1,377 +1,2001 @@
-use std::io::{self, Write};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
 
 use log::info;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::{ast::Node, symbol_table::SymbolTable, Num, Op, Value};
+use crate::{
+    ast::{Node, Param, Passing, Visibility},
+    error::RuntimeError,
+    file::FileHandle,
+    object::ObjectRef,
+    symbol_table::{Location, Reference, SymbolTable, Variable},
+    Num, Op, Position, Value,
+};
+
+/// A user defined function or procedure
+#[derive(Clone, Debug)]
+struct Subroutine {
+    params: Vec<Param>,
+    body: Node,
+    is_function: bool,
+}
+
+/// A user defined class. Methods are stored by name, along with their visibility.
+/// Inherited members are not copied in, but found by following `parent`.
+#[derive(Clone, Debug)]
+struct Class {
+    parent: Option<(String, Position)>,
+    attributes: Vec<(String, Visibility)>,
+    methods: HashMap<String, (Subroutine, Visibility)>,
+}
+
+/// Describes where execution should continue after a statement has run
+enum Flow {
+    Next,
+    /// The value returned, if any, and the position of the `return`
+    Return(Option<Value>, Position),
+}
 
 pub struct Interpretor {
     ast: Box<Node>,
+    input: String,
     symbol_table: SymbolTable,
+    subroutines: HashMap<String, Subroutine>,
+    classes: HashMap<String, Class>,
+    /// The directory that file paths in the program are relative to
+    working_dir: PathBuf,
+    rng: StdRng,
 }
 
 impl Interpretor {
-    pub fn new(ast: Box<Node>) -> Self {
+    pub fn new(ast: Box<Node>, input: String) -> Self {
         Self {
             ast,
+            input,
             symbol_table: SymbolTable::new(),
+            subroutines: HashMap::new(),
+            classes: HashMap::new(),
+            working_dir: PathBuf::from("."),
+            rng: StdRng::from_entropy(),
         }
     }
 
-    pub fn run(&mut self) {
+    /// Seeds the random number generator, so `random` gives the same numbers every run
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Sets the directory that file paths in the program are relative to
+    pub fn with_working_dir(mut self, dir: PathBuf) -> Self {
+        self.working_dir = dir;
+        self
+    }
+
+    pub fn run(&mut self) -> Result<(), RuntimeError> {
         info!("Running program");
         match *self.ast.clone() {
             Node::Block(nodes) => {
-                for node in nodes {
-                    self.run_node(node);
+                // subroutines may be called before they are declared
+                for node in nodes.iter() {
+                    self.declare_subroutine(node.clone());
+                    self.declare_class(node.clone());
+                }
+                // superclasses may also be declared after the classes inheriting from them
+                for node in nodes.iter() {
+                    if let Node::ClassDecl { ident, .. } = node {
+                        self.check_inheritance(ident)?;
+                    }
+                }
+                if let Flow::Return(_, pos) = self.run_block(nodes)? {
+                    return Err(RuntimeError::ReturnOutsideSubroutine(
+                        pos,
+                        self.input.clone(),
+                    ));
                 }
             }
             _ => panic!("Code must be in a block"),
         }
+        Ok(())
     }
 
-    fn run_node(&mut self, node: Node) {
+    fn run_node(&mut self, node: Node) -> Result<Flow, RuntimeError> {
         info!("Running node");
         match node {
-            // TODO: Variables - requires symbol table
             Node::FuncCall { .. } => {
-                self.run_func(node);
-            }
-            Node::Assign { .. } => self.run_assign(node),
-            Node::ArrayAssign { .. } => self.run_array_assign(node),
-            Node::ArrayAssingIndex { .. } => self.run_array_assign_ind(node),
-            Node::IfExpr { .. } => self.run_if(node),
-            Node::WhileStmt { .. } => self.run_while(node),
-            Node::Block(nodes) => self.run_block(nodes),
+                self.run_func(node)?;
+            }
+            Node::Assign { .. } => self.run_assign(node)?,
+            Node::ArrayAssign { .. } => self.run_array_assign(node)?,
+            Node::ArrayLiteralAssign { ident, elements } => {
+                let array = self.get_expr_val(Node::ArrayLiteral(elements))?;
+                self.symbol_table
+                    .declare_local(ident, Variable::Value(array));
+            }
+            Node::ArrayAssingIndex { .. } => self.run_array_assign_ind(node)?,
+            Node::IfExpr { .. } => return self.run_if(node),
+            Node::WhileStmt { .. } => return self.run_while(node),
+            Node::DoUntil { .. } => return self.run_do_until(node),
+            Node::ForStmt { .. } => return self.run_for(node),
+            Node::Switch { .. } => return self.run_switch(node),
+            Node::FunctionDecl { .. } | Node::ProcedureDecl { .. } => self.declare_subroutine(node),
+            Node::ClassDecl { ref ident, .. } => {
+                let ident = ident.clone();
+                self.declare_class(node);
+                self.check_inheritance(&ident)?;
+            }
+            Node::SuperCall { .. } => {
+                self.run_super_call(node)?;
+            }
+            Node::AttributeAssign { .. } => self.run_attribute_assign(node)?,
+            Node::Return { value, pos } => {
+                let value = match value {
+                    Some(expr) => Some(self.get_expr_val(*expr)?),
+                    None => None,
+                };
+                return Ok(Flow::Return(value, pos));
+            }
+            Node::GlobalDecl { ident, value } => {
+                let value = self.get_expr_val(*value)?;
+                self.symbol_table.declare_global(ident, value);
+            }
+            Node::Block(nodes) => return self.run_block(nodes),
+            Node::DotExpr { .. } => {
+                self.run_dot_expr(node)?;
+            }
             _ => todo!("more node types"),
         }
+        Ok(Flow::Next)
     }
 
-    fn run_block(&mut self, nodes: Vec<Node>) {
+    fn run_block(&mut self, nodes: Vec<Node>) -> Result<Flow, RuntimeError> {
         info!("Running block");
         for node in nodes {
-            self.run_node(node);
+            if let flow @ Flow::Return(..) = self.run_node(node)? {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn declare_subroutine(&mut self, node: Node) {
+        if let Some((ident, subroutine)) = self.subroutine_from_decl(node) {
+            info!("Declaring subroutine: {}", ident);
+            self.subroutines.insert(ident, subroutine);
+        }
+    }
+
+    fn subroutine_from_decl(&self, node: Node) -> Option<(String, Subroutine)> {
+        match node {
+            Node::FunctionDecl {
+                ident,
+                params,
+                body,
+            } => Some((
+                ident,
+                Subroutine {
+                    params,
+                    body: *body,
+                    is_function: true,
+                },
+            )),
+            Node::ProcedureDecl {
+                ident,
+                params,
+                body,
+            } => Some((
+                ident,
+                Subroutine {
+                    params,
+                    body: *body,
+                    is_function: false,
+                },
+            )),
+            _ => None,
         }
     }
 
-    fn run_if(&mut self, node: Node) {
+    fn declare_class(&mut self, node: Node) {
+        let (ident, parent, attributes, methods) = match node {
+            Node::ClassDecl {
+                ident,
+                parent,
+                attributes,
+                methods,
+            } => (ident, parent, attributes, methods),
+            _ => return,
+        };
+        info!("Declaring class: {}", ident);
+
+        let methods = methods
+            .into_iter()
+            .filter_map(|(node, visibility)| {
+                self.subroutine_from_decl(node)
+                    .map(|(name, subroutine)| (name, (subroutine, visibility)))
+            })
+            .collect();
+        self.classes.insert(
+            ident,
+            Class {
+                parent,
+                attributes,
+                methods,
+            },
+        );
+    }
+
+    /// Checks that every class `class` inherits from exists, and that it
+    /// doesn't end up inheriting from itself
+    fn check_inheritance(&self, class: &str) -> Result<(), RuntimeError> {
+        let mut seen = vec![class.to_string()];
+        let mut current = class.to_string();
+        // PANIC: only declared classes are checked, and their superclasses are checked below
+        while let Some((parent, pos)) = self.classes.get(&current).unwrap().parent.clone() {
+            if !self.classes.contains_key(&parent) {
+                return Err(RuntimeError::UnknownSuperclass(
+                    pos,
+                    current,
+                    parent,
+                    self.input.clone(),
+                ));
+            }
+            if seen.contains(&parent) {
+                return Err(RuntimeError::CyclicInheritance(
+                    pos,
+                    parent,
+                    self.input.clone(),
+                ));
+            }
+            seen.push(parent.clone());
+            current = parent;
+        }
+        Ok(())
+    }
+
+    fn run_if(&mut self, node: Node) -> Result<Flow, RuntimeError> {
         info!("Running if");
-        let (expr, then, els) = match node {
-            Node::IfExpr { expr, then, els } => (expr, then, els),
+        let (expr, then, elseifs, els, pos) = match node {
+            Node::IfExpr {
+                expr,
+                then,
+                elseifs,
+                els,
+                pos,
+            } => (expr, then, elseifs, els, pos),
             _ => panic!("Not if statement"),
         };
 
-        let condition = self.run_expr(*expr);
-        match condition {
-            Value::Boolean(true) => {
-                info!("If expression is true!");
-                self.run_node(*then);
-            }
-            Value::Boolean(false) => {
-                info!("If expression is false.");
-                self.run_node(*els);
+        if self.evaluate_condition(*expr, "if", pos)? {
+            info!("If expression is true!");
+            return self.run_node(*then);
+        }
+        // the first elseif with a true condition is the only one that runs
+        for (expr, body, pos) in elseifs {
+            if self.evaluate_condition(expr, "elseif", pos)? {
+                info!("Elseif expression is true!");
+                return self.run_node(body);
             }
-            _ => panic!("Unsupported expression as condition: {}", condition),
         }
+        info!("If expression is false.");
+        self.run_node(*els)
     }
 
-    fn run_while(&mut self, node: Node) {
+    fn run_while(&mut self, node: Node) -> Result<Flow, RuntimeError> {
         info!("Running while");
-        let (expr, body) = match node {
-            Node::WhileStmt { expr, body } => (expr, body),
+        let (expr, body, pos) = match node {
+            Node::WhileStmt { expr, body, pos } => (expr, body, pos),
             _ => panic!("Not a while statement"),
         };
 
-        while self.evaluate_condition(*expr.clone()) {
-            self.run_node(*body.clone());
+        while self.evaluate_condition(*expr.clone(), "while", pos)? {
+            if let flow @ Flow::Return(..) = self.run_node(*body.clone())? {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn run_switch(&mut self, node: Node) -> Result<Flow, RuntimeError> {
+        info!("Running switch");
+        let (expr, cases, default) = match node {
+            Node::Switch {
+                expr,
+                cases,
+                default,
+            } => (expr, cases, default),
+            _ => panic!("Not a switch statement"),
+        };
+
+        let subject = self.get_expr_val(*expr)?;
+        for (value, body) in cases {
+            // cases are matched with the same equality as `==`
+            let value = self.get_expr_val(value)?;
+            if self.equal(&subject, &value) {
+                return self.run_node(body);
+            }
+        }
+
+        match default {
+            Some(body) => self.run_node(*body),
+            None => Ok(Flow::Next),
         }
     }
 
-    fn run_func(&mut self, node: Node) -> Option<Value> {
+    fn run_do_until(&mut self, node: Node) -> Result<Flow, RuntimeError> {
+        info!("Running do until");
+        let (body, expr, pos) = match node {
+            Node::DoUntil { body, expr, pos } => (body, expr, pos),
+            _ => panic!("Not a do until statement"),
+        };
+
+        // the body always runs at least once, as the condition is checked afterwards
+        loop {
+            if let flow @ Flow::Return(..) = self.run_node(*body.clone())? {
+                return Ok(flow);
+            }
+            if self.evaluate_condition(*expr.clone(), "until", pos)? {
+                break;
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn run_for(&mut self, node: Node) -> Result<Flow, RuntimeError> {
+        info!("Running for");
+        let (ident, start, end, step, body, pos) = match node {
+            Node::ForStmt {
+                ident,
+                start,
+                end,
+                step,
+                body,
+                pos,
+            } => (ident, start, end, step, body, pos),
+            _ => panic!("Not a for loop"),
+        };
+
+        let start = self.get_expr_val(*start)?;
+        let start = self.expect_for_integer("start", start, pos)?;
+        // the end and step are only evaluated once, before the loop starts
+        let end = self.get_expr_val(*end)?;
+        let end = self.expect_for_integer("end", end, pos)?;
+        let step = match step {
+            Some(x) => {
+                let step = self.get_expr_val(*x)?;
+                self.expect_for_integer("step", step, pos)?
+            }
+            None => 1,
+        };
+        if step == 0 {
+            return Err(RuntimeError::ForStepZero(pos, self.input.clone()));
+        }
+
+        let mut counter = start;
+        // a negative step counts down towards the end value
+        while (step > 0 && counter <= end) || (step < 0 && counter >= end) {
+            self.set_variable(ident.clone(), Value::Number(counter), pos)?;
+            if let flow @ Flow::Return(..) = self.run_node(*body.clone())? {
+                return Ok(flow);
+            }
+
+            counter = match counter.checked_add(step) {
+                Some(x) => x,
+                None => break,
+            };
+        }
+        Ok(Flow::Next)
+    }
+
+    /// The start, end and step of a `for` loop must all be integers
+    fn expect_for_integer(
+        &self,
+        part: &'static str,
+        value: Value,
+        pos: Position,
+    ) -> Result<Num, RuntimeError> {
+        match value {
+            Value::Number(x) => Ok(x),
+            x => Err(RuntimeError::InvalidForValue(
+                pos,
+                part,
+                x.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    fn run_func(&mut self, node: Node) -> Result<Option<Value>, RuntimeError> {
         info!("Running function");
-        let (ident, args) = match node {
-            Node::FuncCall { ident, args } => (ident, args),
+        let (ident, args, pos) = match node {
+            Node::FuncCall { ident, args, pos } => (ident, args, pos),
             _ => panic!("Not a function"),
         };
         // built in functions
         match ident.as_str() {
             "print" => {
-                self.builtin_print(args);
-                None
+                self.builtin_print(args)?;
+                Ok(None)
+            }
+            "input" => Ok(Some(self.builtin_input(args, pos)?)),
+            "int" => Ok(Some(self.builtin_casti(args, pos)?)),
+            "float" | "real" => Ok(Some(self.builtin_castr(&ident, args, pos)?)),
+            "str" => Ok(Some(self.builtin_casts(args, pos)?)),
+            "bool" => Ok(Some(self.builtin_castb(args, pos)?)),
+            "ASC" => Ok(Some(self.builtin_asc(args, pos)?)),
+            "CHR" => Ok(Some(self.builtin_chr(args, pos)?)),
+            "openRead" | "openWrite" => Ok(Some(self.builtin_open(&ident, args, pos)?)),
+            "random" => Ok(Some(self.builtin_random(args, pos)?)),
+            "newFile" => {
+                self.builtin_new_file(args, pos)?;
+                Ok(None)
+            }
+            _ => {
+                // inside a method, other methods of the object can be called by name
+                if let Some((object, _)) = self.symbol_table.current_method() {
+                    if let Some((subroutine, visibility, declared_in)) =
+                        self.find_method(&object.class(), &ident)
+                    {
+                        self.check_access(&ident, visibility, declared_in.clone(), pos)?;
+                        return self.call_subroutine(
+                            ident,
+                            subroutine,
+                            args,
+                            pos,
+                            Some((object, declared_in)),
+                        );
+                    }
+                }
+                self.run_subroutine(ident, args, pos)
+            }
+        }
+    }
+
+    fn run_subroutine(
+        &mut self,
+        ident: String,
+        args: Vec<Node>,
+        pos: Position,
+    ) -> Result<Option<Value>, RuntimeError> {
+        info!("Running subroutine: {}", ident);
+        let subroutine = match self.subroutines.get(&ident) {
+            Some(x) => x.clone(),
+            None => {
+                return Err(RuntimeError::UnknownSubroutine(
+                    pos,
+                    ident,
+                    self.input.clone(),
+                ))
+            }
+        };
+        self.call_subroutine(ident, subroutine, args, pos, None)
+    }
+
+    /// Calls a subroutine, or a method when `receiver` gives the object it is
+    /// called on and the class the method was declared in
+    fn call_subroutine(
+        &mut self,
+        ident: String,
+        subroutine: Subroutine,
+        args: Vec<Node>,
+        pos: Position,
+        receiver: Option<(ObjectRef, String)>,
+    ) -> Result<Option<Value>, RuntimeError> {
+        if args.len() != subroutine.params.len() {
+            return Err(RuntimeError::WrongArgumentCount(
+                pos,
+                ident,
+                subroutine.params.len(),
+                args.len(),
+                self.input.clone(),
+            ));
+        }
+
+        // arguments are evaluated in the caller's scope, before the call gets its own.
+        // byRef arguments are bound to the caller's variable or element instead.
+        let mut variables = Vec::new();
+        for (param, arg) in subroutine.params.iter().zip(args) {
+            let (value, target) = match arg {
+                Node::VariableRef { ident, pos } => (
+                    self.get_variable(ident.clone(), pos)?,
+                    Some((ident, Vec::new(), pos)),
+                ),
+                Node::ArrayRef {
+                    ident,
+                    indices,
+                    pos,
+                } => {
+                    let indices = self.eval_indices(&ident, indices, pos)?;
+                    let value = self.get_index(&ident, &indices, pos)?;
+                    (value, Some((ident, indices, pos)))
+                }
+                x => (self.get_expr_val(x)?, None),
+            };
+
+            let by_ref = match param.passing {
+                Passing::ByRef if target.is_none() => {
+                    return Err(RuntimeError::ByRefNeedsVariable(
+                        pos,
+                        ident,
+                        param.ident.clone(),
+                        self.input.clone(),
+                    ))
+                }
+                Passing::ByRef => true,
+                Passing::ByVal => false,
+                // arrays are passed by reference unless stated otherwise
+                Passing::Default => matches!(value, Value::Array(_)),
+            };
+            variables.push(match target {
+                Some((ident, indices, pos)) if by_ref => {
+                    Variable::Reference(self.reference_to(ident, indices, pos)?)
+                }
+                _ => Variable::Value(value),
+            });
+        }
+
+        match receiver {
+            Some((object, class)) => self.symbol_table.push_method_scope(object, class),
+            None => self.symbol_table.push_scope(),
+        }
+        for (param, variable) in subroutine.params.iter().zip(variables) {
+            self.symbol_table
+                .declare_local(param.ident.clone(), variable);
+        }
+        let flow = self.run_node(subroutine.body);
+        self.symbol_table.pop_scope();
+        let flow = flow?;
+
+        match flow {
+            Flow::Return(Some(x), _) if subroutine.is_function => Ok(Some(x)),
+            Flow::Return(Some(_), _) => Err(RuntimeError::ProcedureReturn(
+                pos,
+                ident,
+                self.input.clone(),
+            )),
+            _ if subroutine.is_function => {
+                Err(RuntimeError::MissingReturn(pos, ident, self.input.clone()))
             }
-            "input" => Some(self.builtin_input(args)),
-            "int" => Some(self.builtin_casti(args)),
-            _ => todo!("Implement custom functions"),
+            _ => Ok(None),
         }
     }
 
-    fn run_assign(&mut self, node: Node) {
+    fn run_assign(&mut self, node: Node) -> Result<(), RuntimeError> {
         info!("Assigning value");
-        let (ident, rexpr) = match node {
-            Node::Assign { ident, value } => (ident, value),
+        let (ident, rexpr, pos) = match node {
+            Node::Assign { ident, value, pos } => (ident, value, pos),
             _ => panic!("Not an assign"),
         };
         // get value to put in symbol table
         match *rexpr.clone() {
             Node::BinaryExpr { .. } => {
-                let rvalue = self.run_expr(*rexpr);
-                self.symbol_table.assign_variable(ident, rvalue);
+                let rvalue = self.run_expr(*rexpr)?;
+                self.set_variable(ident, rvalue, pos)?;
             }
-            Node::VariableRef(_) => {
-                let rvalue = self.get_expr_val(*rexpr.clone());
-                self.symbol_table.assign_variable(ident, rvalue);
+            Node::UnaryExpr { .. } => {
+                let rvalue = self.run_unary_expr(*rexpr)?;
+                self.set_variable(ident, rvalue, pos)?;
             }
-            Node::ArrayRef { .. } => {
-                let rvalue = self.get_array_ref(*rexpr);
-                self.symbol_table.assign_variable(ident, rvalue);
+            Node::VariableRef { .. } => {
+                let rvalue = self.get_expr_val(*rexpr.clone())?;
+                self.set_variable(ident, rvalue, pos)?;
             }
-            Node::DotExpr { .. } => {
-                let rvalue = self.run_dot_expr(*rexpr);
-                self.symbol_table.assign_variable(ident, rvalue);
+            Node::ArrayRef { .. } => {
+                let rvalue = self.get_array_ref(*rexpr)?;
+                self.set_variable(ident, rvalue, pos)?;
             }
-            Node::FuncCall { .. } => {
-                let rvalue = self.run_func(*rexpr).expect("function has no return value");
-                self.symbol_table.assign_variable(ident, rvalue);
+            Node::DotExpr { .. }
+            | Node::FuncCall { .. }
+            | Node::ArrayLiteral(_)
+            | Node::New { .. }
+            | Node::SuperCall { .. } => {
+                let rvalue = self.get_expr_val(*rexpr)?;
+                self.set_variable(ident, rvalue, pos)?;
             }
-            Node::Primary(x) => self.symbol_table.assign_variable(ident, x),
+            Node::Primary(x) => self.set_variable(ident, x, pos)?,
             _ => panic!("unsupported rvalue for assign: {:?}", *rexpr.clone()),
         }
+        Ok(())
     }
 
-    fn run_array_assign(&mut self, node: Node) {
+    fn run_array_assign(&mut self, node: Node) -> Result<(), RuntimeError> {
         info!("Creating array");
-        let (ident, size) = match node {
-            Node::ArrayAssign { ident, size } => (ident, size),
+        let (ident, sizes, pos) = match node {
+            Node::ArrayAssign { ident, sizes, pos } => (ident, sizes, pos),
             _ => panic!("Not an assign"),
         };
 
-        let numeric_size = match self.get_expr_val(*size) {
-            Value::Number(x) => x,
-            _ => panic!("Array size must be numeric"),
-        };
+        let mut numeric_sizes = Vec::new();
+        for size in sizes {
+            match self.get_expr_val(size)? {
+                Value::Number(x) if x >= 0 => numeric_sizes.push(x),
+                x => {
+                    return Err(RuntimeError::InvalidArraySize(
+                        pos,
+                        ident,
+                        x.to_string(),
+                        self.input.clone(),
+                    ))
+                }
+            }
+        }
 
-        // create vector of size, with all parts initialised as 0
-        let array = std::iter::repeat(Value::Number(0))
-            .take(numeric_size as usize)
-            .collect::<Vec<_>>();
+        // build the array from the innermost dimension outwards, with all parts initialised as 0
+        let array = numeric_sizes
+            .iter()
+            .rev()
+            .fold(Value::Number(0), |inner, size| {
+                Value::Array(vec![inner; *size as usize])
+            });
 
+        // declaring an array always makes a new variable, even if a global has the same name
         self.symbol_table
-            .assign_variable(ident, Value::Array(array));
+            .declare_local(ident, Variable::Value(array));
 
         info!("Symbol table: {:#?}", self.symbol_table);
+        Ok(())
     }
 
-    fn run_array_assign_ind(&mut self, node: Node) {
+    fn run_array_assign_ind(&mut self, node: Node) -> Result<(), RuntimeError> {
         info!("Assigning array index");
-        let (ident, index, value) = match node {
+        let (ident, indices, value, pos) = match node {
             Node::ArrayAssingIndex {
                 ident,
-                index,
+                indices,
                 value,
-            } => (ident, index, value),
+                pos,
+            } => (ident, indices, value, pos),
             _ => panic!("Not an array index assign"),
         };
 
-        let numeric_index = match self.get_expr_val(*index) {
-            Value::Number(x) => x,
-            _ => panic!("Index must be numeric"),
-        };
+        let indices = self.eval_indices(&ident, indices, pos)?;
+        let value = self.get_expr_val(*value)?;
+        self.assign_index(ident, indices, value, pos)
+    }
+
+    /// Assigns to an element of an array, given one index per dimension
+    fn assign_index(
+        &mut self,
+        ident: String,
+        indices: Vec<Num>,
+        value: Value,
+        pos: Position,
+    ) -> Result<(), RuntimeError> {
+        let mut array = self.get_variable(ident.clone(), pos)?;
+        self.set_element(&ident, &mut array, &indices, value, pos)?;
+        self.set_variable(ident, array, pos)
+    }
 
-        let value = self.get_expr_val(*value);
+    /// Replaces the element of `array` found by walking down one dimension per index
+    fn set_element(
+        &self,
+        ident: &str,
+        array: &mut Value,
+        indices: &[Num],
+        value: Value,
+        pos: Position,
+    ) -> Result<(), RuntimeError> {
+        let mut element = array;
+        for index in indices.iter().copied() {
+            element = match element {
+                Value::Array(x) => {
+                    let i = self.array_index(ident, index, x.len(), pos)?;
+                    &mut x[i]
+                }
+                Value::String(_) => {
+                    return Err(RuntimeError::StringIndexAssignment(pos, self.input.clone()))
+                }
+                x => {
+                    return Err(RuntimeError::NotIndexable(
+                        pos,
+                        x.type_name(),
+                        self.input.clone(),
+                    ))
+                }
+            };
+        }
+        *element = value;
+        Ok(())
+    }
 
-        let mut vec = match self.symbol_table.get_variable(ident.clone()) {
-            Value::Array(x) => x,
-            _ => panic!("Cannot index into non array type"),
-        };
-        vec[numeric_index as usize] = value;
-        self.symbol_table.assign_variable(ident, Value::Array(vec));
+    fn eval_indices(
+        &mut self,
+        ident: &str,
+        indices: Vec<Node>,
+        pos: Position,
+    ) -> Result<Vec<Num>, RuntimeError> {
+        let mut numeric_indices = Vec::new();
+        for index in indices {
+            match self.get_expr_val(index)? {
+                Value::Number(x) => numeric_indices.push(x),
+                x => {
+                    return Err(RuntimeError::InvalidIndex(
+                        pos,
+                        ident.to_string(),
+                        x.type_name(),
+                        self.input.clone(),
+                    ))
+                }
+            }
+        }
+        Ok(numeric_indices)
     }
 
-    fn run_expr(&mut self, node: Node) -> Value {
+    /// Checks an index lies within an array of the given length
+    fn array_index(
+        &self,
+        ident: &str,
+        index: Num,
+        length: usize,
+        pos: Position,
+    ) -> Result<usize, RuntimeError> {
+        match usize::try_from(index) {
+            Ok(x) if x < length => Ok(x),
+            _ => Err(RuntimeError::ArrayIndexOutOfRange(
+                pos,
+                ident.to_string(),
+                index,
+                length,
+                self.input.clone(),
+            )),
+        }
+    }
+
+    fn run_expr(&mut self, node: Node) -> Result<Value, RuntimeError> {
         info!("Running expression: {:?}", node);
-        let (left, op, right) = match node {
+        let (left, op, right, pos) = match node {
             Node::BinaryExpr {
                 left,
                 operator,
                 right,
-            } => (left, operator, right),
+                pos,
+            } => (left, operator, right, pos),
             _ => panic!("Not an expression"),
         };
 
-        let lvalue = self.get_expr_val(*left);
-        let rvalue = self.get_expr_val(*right);
+        let lvalue = self.get_expr_val(*left)?;
+        // AND and OR short circuit, so the right hand side is only evaluated when needed
+        match op {
+            Op::And | Op::Or => {
+                let lbool = self.expect_boolean(op.symbol(), lvalue, pos)?;
+                if lbool == (op == Op::Or) {
+                    return Ok(Value::Boolean(lbool));
+                }
+                let rvalue = self.get_expr_val(*right)?;
+                return Ok(Value::Boolean(self.expect_boolean(
+                    op.symbol(),
+                    rvalue,
+                    pos,
+                )?));
+            }
+            _ => (),
+        }
+        let rvalue = self.get_expr_val(*right)?;
 
+        self.apply_op(lvalue, op, rvalue, pos)
+    }
+
+    fn run_unary_expr(&mut self, node: Node) -> Result<Value, RuntimeError> {
+        info!("Running unary expression: {:?}", node);
+        let (op, expr, pos) = match node {
+            Node::UnaryExpr {
+                operator,
+                expr,
+                pos,
+            } => (operator, expr, pos),
+            _ => panic!("Not a unary expression"),
+        };
+
+        let value = self.get_expr_val(*expr)?;
+        match (op, value) {
+            (Op::Not, value) => Ok(Value::Boolean(!self.expect_boolean("NOT", value, pos)?)),
+            (Op::Plus, Value::Number(x)) => Ok(Value::Number(x)),
+            (Op::Plus, Value::Real(x)) => Ok(Value::Real(x)),
+            (Op::Minus, Value::Number(x)) => x
+                .checked_neg()
+                .map(Value::Number)
+                .ok_or_else(|| RuntimeError::Overflow(pos, self.input.clone())),
+            (Op::Minus, Value::Real(x)) => Ok(Value::Real(-x)),
+            (op, value) => Err(RuntimeError::InvalidOperand(
+                pos,
+                op.symbol(),
+                "an integer or real",
+                value.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Boolean operators and conditions can only be given boolean values.
+    /// `name` is the operator or keyword the value was given to.
+    fn expect_boolean(
+        &self,
+        name: &'static str,
+        value: Value,
+        pos: Position,
+    ) -> Result<bool, RuntimeError> {
+        match value {
+            Value::Boolean(x) => Ok(x),
+            x => Err(RuntimeError::InvalidOperand(
+                pos,
+                name,
+                "a boolean",
+                x.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Applies a binary operator to two already evaluated values
+    fn apply_op(
+        &mut self,
+        lvalue: Value,
+        op: Op,
+        rvalue: Value,
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
         info!("lv: {:?}, op: {:?}, rv: {:?}", lvalue, op, rvalue);
 
-        match lvalue {
-            Value::Number(x) => match rvalue {
-                Value::Number(y) => match op {
-                    Op::Plus => Value::Number(x + y),
-                    Op::Minus => Value::Number(x - y),
-                    Op::Multiply => Value::Number(x * y),
-                    Op::Divide => Value::Number(x / y),
-                    Op::Mod => Value::Number(x % y),
-                    Op::EqualTo => Value::Boolean(x == y),
-                    Op::Less => Value::Boolean(x < y),
-                    Op::LessEqual => Value::Boolean(x <= y),
-                    Op::Greater => Value::Boolean(x > y),
-                    Op::GreaterEqual => Value::Boolean(x >= y),
-                    _ => panic!("Invalid arithmetic expression"),
-                },
-                Value::String(_) => self.concat(lvalue, rvalue),
-                _ => panic!(),
-            },
-            Value::String(_) => self.concat(lvalue, rvalue),
-            _ => panic!(),
+        match op {
+            // values of different types are never equal, and arrays are compared element-wise
+            Op::EqualTo => return Ok(Value::Boolean(self.equal(&lvalue, &rvalue))),
+            Op::NotEqualTo => return Ok(Value::Boolean(!self.equal(&lvalue, &rvalue))),
+            Op::Less | Op::LessEqual | Op::Greater | Op::GreaterEqual => {
+                // comparisons involving NaN are always false
+                let ordering = match self.compare(&lvalue, &op, &rvalue, pos)? {
+                    Some(x) => x,
+                    None => return Ok(Value::Boolean(false)),
+                };
+                return Ok(Value::Boolean(match op {
+                    Op::Less => ordering.is_lt(),
+                    Op::LessEqual => ordering.is_le(),
+                    Op::Greater => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                }));
+            }
+            _ => (),
+        }
+
+        if matches!(op, Op::Divide | Op::IntDivide | Op::Mod) && self.is_zero(&rvalue) {
+            return Err(RuntimeError::DivisionByZero(pos, self.input.clone()));
+        }
+
+        match (&lvalue, &rvalue) {
+            (Value::Number(x), Value::Number(y)) => {
+                let result = match op {
+                    Op::Plus => x.checked_add(*y),
+                    Op::Minus => x.checked_sub(*y),
+                    Op::Multiply => x.checked_mul(*y),
+                    // dividing two integers still gives a real result
+                    Op::Divide => return Ok(Value::Real(*x as f64 / *y as f64)),
+                    Op::IntDivide => self.floor_div(*x, *y),
+                    Op::Mod => self
+                        .floor_div(*x, *y)
+                        .and_then(|q| q.checked_mul(*y))
+                        .and_then(|q| x.checked_sub(q)),
+                    // a negative exponent gives a fractional result
+                    Op::Power if *y < 0 => return Ok(Value::Real((*x as f64).powf(*y as f64))),
+                    Op::Power => self.checked_power(*x, *y),
+                    _ => unreachable!("boolean operators are applied in run_expr"),
+                };
+                result
+                    .map(Value::Number)
+                    .ok_or_else(|| RuntimeError::Overflow(pos, self.input.clone()))
+            }
+            (Value::String(_), _) | (_, Value::String(_)) if op == Op::Plus => {
+                Ok(self.concat(lvalue, rvalue))
+            }
+            // if either side is a real, the integer side is promoted to a real
+            (Value::Number(_) | Value::Real(_), Value::Number(_) | Value::Real(_)) => {
+                let (x, y) = (self.as_real(&lvalue), self.as_real(&rvalue));
+                Ok(match op {
+                    Op::Plus => Value::Real(x + y),
+                    Op::Minus => Value::Real(x - y),
+                    Op::Multiply => Value::Real(x * y),
+                    Op::Divide => Value::Real(x / y),
+                    Op::IntDivide => Value::Real((x / y).floor()),
+                    Op::Mod => Value::Real(x - (x / y).floor() * y),
+                    Op::Power => Value::Real(x.powf(y)),
+                    _ => unreachable!("boolean operators are applied in run_expr"),
+                })
+            }
+            _ => Err(RuntimeError::InvalidOperands(
+                pos,
+                op.symbol(),
+                lvalue.type_name(),
+                rvalue.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Integer division rounding towards negative infinity, so that
+    /// `x == (x DIV y) * y + x MOD y` always holds. Returns `None` on overflow.
+    fn floor_div(&self, x: Num, y: Num) -> Option<Num> {
+        let quotient = x.checked_div(y)?;
+        if x % y != 0 && (x < 0) != (y < 0) {
+            Some(quotient - 1)
+        } else {
+            Some(quotient)
+        }
+    }
+
+    /// Raises `base` to a non-negative `exponent` by repeated squaring, so
+    /// exponents too large for `u32` still work for bases 0, 1 and -1.
+    /// Returns `None` on overflow.
+    fn checked_power(&self, mut base: Num, mut exponent: Num) -> Option<Num> {
+        let mut result: Num = 1;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exponent /= 2;
+            // the last squaring is skipped, as it would never be used
+            if exponent > 0 {
+                base = base.checked_mul(base)?;
+            }
         }
+        Some(result)
     }
 
-    fn get_expr_val(&mut self, node: Node) -> Value {
+    fn is_zero(&self, value: &Value) -> bool {
+        match value {
+            Value::Number(x) => *x == 0,
+            Value::Real(x) => *x == 0.0,
+            _ => false,
+        }
+    }
+
+    /// Compares two values for equality, treating integers and reals with the
+    /// same value as equal
+    fn equal(&self, lvalue: &Value, rvalue: &Value) -> bool {
+        match (lvalue, rvalue) {
+            (Value::Number(_), Value::Real(_)) | (Value::Real(_), Value::Number(_)) => {
+                self.as_real(lvalue) == self.as_real(rvalue)
+            }
+            (Value::Array(x), Value::Array(y)) => {
+                x.len() == y.len() && x.iter().zip(y).all(|(x, y)| self.equal(x, y))
+            }
+            _ => lvalue == rvalue,
+        }
+    }
+
+    /// Orders two values of the same type for the comparison `op`. Strings are
+    /// ordered lexicographically and `false` is ordered before `true`. Returns
+    /// `None` if either side is NaN.
+    fn compare(
+        &self,
+        lvalue: &Value,
+        op: &Op,
+        rvalue: &Value,
+        pos: Position,
+    ) -> Result<Option<Ordering>, RuntimeError> {
+        Ok(match (lvalue, rvalue) {
+            (Value::Number(x), Value::Number(y)) => Some(x.cmp(y)),
+            (Value::Number(_) | Value::Real(_), Value::Number(_) | Value::Real(_)) => {
+                self.as_real(lvalue).partial_cmp(&self.as_real(rvalue))
+            }
+            (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+            (Value::Boolean(x), Value::Boolean(y)) => Some(x.cmp(y)),
+            _ => {
+                return Err(RuntimeError::InvalidOperands(
+                    pos,
+                    op.symbol(),
+                    lvalue.type_name(),
+                    rvalue.type_name(),
+                    self.input.clone(),
+                ))
+            }
+        })
+    }
+
+    /// Promotes a numeric value to a real
+    fn as_real(&self, value: &Value) -> f64 {
+        match value {
+            Value::Number(x) => *x as f64,
+            Value::Real(x) => *x,
+            _ => panic!("{} is not numeric", value),
+        }
+    }
+
+    /// Looks up a variable that is being read, which must already exist.
+    /// byRef parameters read the caller's variable or element.
+    fn get_variable(&mut self, ident: String, pos: Position) -> Result<Value, RuntimeError> {
+        match self.symbol_table.get_variable(ident.clone()) {
+            Some(Variable::Value(x)) => {
+                self.check_attribute_access(&ident, pos)?;
+                Ok(x)
+            }
+            Some(Variable::Reference(x)) => {
+                // PANIC: references are made to existing variables in frames below the call's
+                let value = self.symbol_table.read(&x.location).unwrap();
+                self.get_element(&x.ident, value, &x.indices, x.pos)
+            }
+            None => Err(RuntimeError::UnknownVariable(
+                pos,
+                ident,
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Assigns to a variable, creating it if needed. byRef parameters write
+    /// to the caller's variable or element.
+    fn set_variable(
+        &mut self,
+        ident: String,
+        value: Value,
+        pos: Position,
+    ) -> Result<(), RuntimeError> {
+        let reference = match self.symbol_table.get_variable(ident.clone()) {
+            Some(Variable::Reference(x)) => x,
+            _ => {
+                self.check_attribute_access(&ident, pos)?;
+                self.symbol_table.assign_variable(ident, value);
+                return Ok(());
+            }
+        };
+        if reference.indices.is_empty() {
+            self.symbol_table.write(&reference.location, value);
+            return Ok(());
+        }
+        // PANIC: references are made to existing variables in frames below the call's
+        let mut array = self.symbol_table.read(&reference.location).unwrap();
+        self.set_element(
+            &reference.ident,
+            &mut array,
+            &reference.indices,
+            value,
+            reference.pos,
+        )?;
+        self.symbol_table.write(&reference.location, array);
+        Ok(())
+    }
+
+    /// Makes a reference to a caller's variable, or an element of it. If the
+    /// variable is itself a byRef parameter, the new reference points straight
+    /// at what that parameter refers to.
+    fn reference_to(
+        &mut self,
+        ident: String,
+        indices: Vec<Num>,
+        pos: Position,
+    ) -> Result<Reference, RuntimeError> {
+        match self.symbol_table.get_variable(ident.clone()) {
+            Some(Variable::Reference(mut x)) => {
+                x.indices.extend(indices);
+                Ok(x)
+            }
+            Some(Variable::Value(_)) => Ok(Reference {
+                // PANIC: the variable was just found, so it can be located
+                location: self.symbol_table.locate(ident.clone()).unwrap(),
+                ident,
+                indices,
+                pos,
+            }),
+            None => Err(RuntimeError::UnknownVariable(
+                pos,
+                ident,
+                self.input.clone(),
+            )),
+        }
+    }
+
+    fn get_expr_val(&mut self, node: Node) -> Result<Value, RuntimeError> {
         info!("Getting numeric value from expression: {:?}", node);
         match node {
             Node::BinaryExpr { .. } => self.run_expr(node),
-            Node::VariableRef(x) => self.symbol_table.get_variable(x),
+            Node::UnaryExpr { .. } => self.run_unary_expr(node),
+            Node::VariableRef { ident, pos } => self.get_variable(ident, pos),
             Node::ArrayRef { .. } => self.get_array_ref(node),
-            Node::FuncCall { .. } => self.run_func(node).unwrap(),
-            Node::DotExpr { .. } => self.run_dot_expr(node),
-            Node::Primary(x) => x,
+            Node::FuncCall { ref ident, pos, .. } => {
+                let ident = ident.clone();
+                let value = self.run_func(node)?;
+                self.expect_return_value(value, ident, pos)
+            }
+            Node::DotExpr { ref right, pos, .. } => {
+                let method = right.clone();
+                let value = self.run_dot_expr(node)?;
+                self.expect_return_value(value, method, pos)
+            }
+            Node::New { .. } => self.run_new(node),
+            Node::SuperCall {
+                ref method, pos, ..
+            } => {
+                let method = method.clone();
+                let value = self.run_super_call(node)?;
+                self.expect_return_value(value, method, pos)
+            }
+            Node::ArrayLiteral(elements) => {
+                let values = elements
+                    .into_iter()
+                    .map(|x| self.get_expr_val(x))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Array(values))
+            }
+            Node::Primary(x) => Ok(x),
             _ => unimplemented!("Unsupported value for expression side"),
         }
     }
 
-    fn run_dot_expr(&mut self, node: Node) -> Value {
+    /// Procedures and methods like `writeLine` give no value, so they can't be
+    /// used as part of an expression
+    fn expect_return_value(
+        &self,
+        value: Option<Value>,
+        ident: String,
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        value.ok_or_else(|| RuntimeError::NoReturnValue(pos, ident, self.input.clone()))
+    }
+
+    fn run_dot_expr(&mut self, node: Node) -> Result<Option<Value>, RuntimeError> {
         info!("Running dot expr");
 
-        let (lvalue, rvalue) = match node {
-            Node::DotExpr { left, right } => (left, right),
+        let (left, right, args, pos) = match node {
+            Node::DotExpr {
+                left,
+                right,
+                args,
+                pos,
+            } => (left, right, args, pos),
             _ => panic!("Not a dot expr"),
         };
 
-        // test for builtin
-        match rvalue.as_str() {
-            "length" => self.builtin_length(lvalue),
-            _ => panic!("Only builtin lvalues are suported"),
+        let value = self.get_expr_val(*left)?;
+        // methods take their arguments unevaluated, as they may be passed byRef
+        if let Value::Object(x) = value {
+            return self.run_object_member(x, right, args, pos);
+        }
+        let mut arg_values = Vec::new();
+        for arg in args.unwrap_or_default() {
+            arg_values.push(self.get_expr_val(arg)?);
+        }
+
+        match value {
+            Value::Array(x) if right == "length" => {
+                self.expect_arg_count(&right, 0, &arg_values, pos)?;
+                Ok(Some(Value::Number(x.len() as Num)))
+            }
+            Value::String(x) => Ok(Some(self.run_string_method(x, right, arg_values, pos)?)),
+            Value::File(x) => self.run_file_method(x, right, arg_values, pos),
+            _ => Err(RuntimeError::UnknownMember(
+                pos,
+                right,
+                value.type_name(),
+                self.input.clone(),
+            )),
         }
     }
 
-    fn get_array_ref(&mut self, node: Node) -> Value {
-        info!("Getting array reference: {:?}", node);
-        let (ident, index) = match node {
-            Node::ArrayRef { ident, index } => (ident, index),
-            _ => panic!("Not an array ref"),
+    fn run_new(&mut self, node: Node) -> Result<Value, RuntimeError> {
+        let (class_name, args, pos) = match node {
+            Node::New { class, args, pos } => (class, args, pos),
+            _ => panic!("Not a new expression"),
         };
+        info!("Creating object: {}", class_name);
 
-        let numeric_index = match self.get_expr_val(*index) {
-            Value::Number(x) => x,
-            _ => panic!("Index must be numeric"),
+        let class = match self.classes.get(&class_name) {
+            Some(x) => x.clone(),
+            None => {
+                return Err(RuntimeError::UnknownClass(
+                    pos,
+                    class_name,
+                    self.input.clone(),
+                ))
+            }
         };
 
-        info!("Array Index: {}", numeric_index);
+        // like arrays, attributes start as 0 until they are assigned.
+        // Objects also get every attribute declared by their superclasses.
+        let mut attributes = HashMap::new();
+        let mut current = Some(class);
+        while let Some(class) = current {
+            for (ident, _) in class.attributes.iter() {
+                attributes.insert(ident.clone(), Value::Number(0));
+            }
+            current = class
+                .parent
+                .and_then(|(parent, _)| self.classes.get(&parent).cloned());
+        }
+        let object = ObjectRef::new(class_name.clone(), attributes);
+
+        if self.find_method(&class_name, "new").is_some() {
+            self.run_object_member(object.clone(), "new".to_string(), Some(args), pos)?;
+        } else if !args.is_empty() {
+            return Err(RuntimeError::WrongArgumentCount(
+                pos,
+                "new".to_string(),
+                0,
+                args.len(),
+                self.input.clone(),
+            ));
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Finds a method by name, starting at `class` and working up through its
+    /// superclasses. Returns the method along with its visibility and the class
+    /// it was declared in.
+    fn find_method(&self, class: &str, method: &str) -> Option<(Subroutine, Visibility, String)> {
+        let class_decl = self.classes.get(class)?;
+        match class_decl.methods.get(method) {
+            Some((subroutine, visibility)) => {
+                Some((subroutine.clone(), visibility.clone(), class.to_string()))
+            }
+            None => self.find_method(&class_decl.parent.as_ref()?.0, method),
+        }
+    }
 
-        let symbol = self.symbol_table.get_variable(ident.to_string());
-        let vec = match symbol {
-            Value::Array(x) => x,
-            _ => panic!("Cannot index into {}", symbol),
+    /// Finds an attribute by name, starting at `class` and working up through its
+    /// superclasses. Returns its visibility and the class it was declared in.
+    fn find_attribute(&self, class: &str, attribute: &str) -> Option<(Visibility, String)> {
+        let class_decl = self.classes.get(class)?;
+        match class_decl
+            .attributes
+            .iter()
+            .find(|(ident, _)| ident == attribute)
+        {
+            Some((_, visibility)) => Some((visibility.clone(), class.to_string())),
+            None => self.find_attribute(&class_decl.parent.as_ref()?.0, attribute),
+        }
+    }
+
+    /// Private members can only be used by methods declared in the same class
+    fn check_access(
+        &self,
+        member: &str,
+        visibility: Visibility,
+        class: String,
+        pos: Position,
+    ) -> Result<(), RuntimeError> {
+        let inside_class = match self.symbol_table.current_method() {
+            Some((_, current)) => current == class,
+            None => false,
         };
-        vec[numeric_index as usize].clone()
+        if visibility == Visibility::Private && !inside_class {
+            return Err(RuntimeError::PrivateMember(
+                pos,
+                member.to_string(),
+                class,
+                self.input.clone(),
+            ));
+        }
+        Ok(())
     }
 
-    fn concat(&mut self, lvalue: Value, rvalue: Value) -> Value {
-        Value::String(format!("{}{}", lvalue, rvalue))
+    /// Checks that a name used inside a method can be used, if it refers to an
+    /// attribute of the current object rather than a variable
+    fn check_attribute_access(&self, ident: &str, pos: Position) -> Result<(), RuntimeError> {
+        if let Some(Location::Attribute(object, _)) = self.symbol_table.locate(ident.to_string()) {
+            // PANIC: objects only have the attributes declared by their class and superclasses
+            let (visibility, declared_in) = self.find_attribute(&object.class(), ident).unwrap();
+            self.check_access(ident, visibility, declared_in, pos)?;
+        }
+        Ok(())
     }
 
-    fn evaluate_condition(&mut self, expr: Node) -> bool {
-        match self.run_expr(expr) {
-            Value::Boolean(x) => x,
-            _ => panic!("Invalid expression for while loop condition"),
+    /// Gets an attribute of an object or calls one of its methods. Methods can
+    /// be called without brackets if they take no arguments.
+    fn run_object_member(
+        &mut self,
+        object: ObjectRef,
+        member: String,
+        args: Option<Vec<Node>>,
+        pos: Position,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let class = object.class();
+        if args.is_none() {
+            if let Some((visibility, declared_in)) = self.find_attribute(&class, &member) {
+                self.check_access(&member, visibility, declared_in, pos)?;
+                return Ok(object.borrow().attributes.get(&member).cloned());
+            }
+        }
+
+        match self.find_method(&class, &member) {
+            Some((subroutine, visibility, declared_in)) => {
+                self.check_access(&member, visibility, declared_in.clone(), pos)?;
+                self.call_subroutine(
+                    member,
+                    subroutine,
+                    args.unwrap_or_default(),
+                    pos,
+                    Some((object, declared_in)),
+                )
+            }
+            None => Err(RuntimeError::UnknownClassMember(
+                pos,
+                member,
+                class,
+                self.input.clone(),
+            )),
         }
     }
 
-    fn builtin_print(&mut self, args: Vec<Node>) {
-        info!("Function was built-in: print");
-        // verify arguments
-        if args.len() == 0 {
-            println!();
-            return;
-        } else if args.len() > 1 {
-            panic!("print cannot accept more than 1 arg!");
+    /// Calls the superclass's version of a method on the current object. The
+    /// superclass is that of the class the running method was declared in, not
+    /// the object's class, so overridden methods can each call the one above.
+    fn run_super_call(&mut self, node: Node) -> Result<Option<Value>, RuntimeError> {
+        let (method, args, pos) = match node {
+            Node::SuperCall { method, args, pos } => (method, args, pos),
+            _ => panic!("Not a super call"),
+        };
+        info!("Running super call: {}", method);
+
+        let (object, class) = match self.symbol_table.current_method() {
+            Some(x) => x,
+            None => {
+                return Err(RuntimeError::SuperOutsideMethod(
+                    pos,
+                    method,
+                    self.input.clone(),
+                ))
+            }
+        };
+        // PANIC: the running method was declared in a class that exists
+        let parent = match self.classes.get(&class).unwrap().parent.clone() {
+            Some((parent, _)) => parent,
+            None => {
+                return Err(RuntimeError::NoSuperclass(
+                    pos,
+                    method,
+                    class,
+                    self.input.clone(),
+                ))
+            }
+        };
+
+        match self.find_method(&parent, &method) {
+            Some((subroutine, visibility, declared_in)) => {
+                self.check_access(&method, visibility, declared_in.clone(), pos)?;
+                self.call_subroutine(method, subroutine, args, pos, Some((object, declared_in)))
+            }
+            None => Err(RuntimeError::UnknownClassMember(
+                pos,
+                method,
+                parent,
+                self.input.clone(),
+            )),
         }
+    }
 
-        let to_print = self.get_expr_val(args[0].clone());
-        println!("{}", to_print);
+    fn run_attribute_assign(&mut self, node: Node) -> Result<(), RuntimeError> {
+        let (object, ident, value, pos) = match node {
+            Node::AttributeAssign {
+                object,
+                ident,
+                value,
+                pos,
+            } => (object, ident, value, pos),
+            _ => panic!("Not an attribute assign"),
+        };
+        info!("Assigning attribute: {}", ident);
+
+        let object = match self.get_expr_val(*object)? {
+            Value::Object(x) => x,
+            x => {
+                return Err(RuntimeError::UnknownMember(
+                    pos,
+                    ident,
+                    x.type_name(),
+                    self.input.clone(),
+                ))
+            }
+        };
+        let class = object.class();
+        let (visibility, declared_in) = match self.find_attribute(&class, &ident) {
+            Some(x) => x,
+            None => {
+                return Err(RuntimeError::UnknownClassMember(
+                    pos,
+                    ident,
+                    class,
+                    self.input.clone(),
+                ))
+            }
+        };
+        self.check_access(&ident, visibility, declared_in, pos)?;
+
+        let value = self.get_expr_val(*value)?;
+        object.borrow_mut().attributes.insert(ident, value);
+        Ok(())
     }
 
-    fn builtin_input(&mut self, args: Vec<Node>) -> Value {
-        info!("Function was built-in: input");
-        if args.len() > 1 {
-            panic!("print cannot accept more than 1 arg!");
+    /// Runs one of the builtin string properties or methods. Strings are indexed
+    /// by character, starting from 0.
+    fn run_string_method(
+        &mut self,
+        string: String,
+        method: String,
+        args: Vec<Value>,
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        let chars: Vec<char> = string.chars().collect();
+        let result = match method.as_str() {
+            "length" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                return Ok(Value::Number(chars.len() as Num));
+            }
+            "upper" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                string.to_uppercase()
+            }
+            "lower" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                string.to_lowercase()
+            }
+            "substring" => {
+                self.expect_arg_count(&method, 2, &args, pos)?;
+                let start = self.expect_integer(&method, &args[0], pos)?;
+                let len = self.expect_integer(&method, &args[1], pos)?;
+                let start = self.char_index(start, chars.len(), pos)?;
+                // a negative length is reported as is, rather than as an end before the start
+                let end = if len < 0 {
+                    len
+                } else {
+                    (start as Num).saturating_add(len)
+                };
+                let end = self.char_index(end, chars.len(), pos)?;
+                chars[start..end].iter().collect()
+            }
+            "left" => {
+                self.expect_arg_count(&method, 1, &args, pos)?;
+                let n = self.expect_integer(&method, &args[0], pos)?;
+                let n = self.char_index(n, chars.len(), pos)?;
+                chars[..n].iter().collect()
+            }
+            "right" => {
+                self.expect_arg_count(&method, 1, &args, pos)?;
+                let n = self.expect_integer(&method, &args[0], pos)?;
+                let n = self.char_index(n, chars.len(), pos)?;
+                chars[chars.len() - n..].iter().collect()
+            }
+            _ => {
+                return Err(RuntimeError::UnknownMember(
+                    pos,
+                    method,
+                    "string",
+                    self.input.clone(),
+                ))
+            }
+        };
+        Ok(Value::String(result))
+    }
+
+    /// Runs one of the methods on a file opened with `openRead` or `openWrite`.
+    /// `writeLine` and `close` do not return a value.
+    fn run_file_method(
+        &mut self,
+        file: FileHandle,
+        method: String,
+        args: Vec<Value>,
+        pos: Position,
+    ) -> Result<Option<Value>, RuntimeError> {
+        let result = match method.as_str() {
+            "readLine" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                match file.read_line() {
+                    Ok(Some(x)) => Ok(Some(Value::String(x))),
+                    Ok(None) => Err("the end of the file has been reached".to_string()),
+                    Err(e) => Err(e),
+                }
+            }
+            "writeLine" => {
+                self.expect_arg_count(&method, 1, &args, pos)?;
+                file.write_line(&args[0].to_string()).map(|_| None)
+            }
+            "endOfFile" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                file.end_of_file().map(|x| Some(Value::Boolean(x)))
+            }
+            "close" => {
+                self.expect_arg_count(&method, 0, &args, pos)?;
+                file.close().map(|_| None)
+            }
+            _ => {
+                return Err(RuntimeError::UnknownMember(
+                    pos,
+                    method,
+                    "file",
+                    self.input.clone(),
+                ))
+            }
+        };
+        result.map_err(|e| RuntimeError::FileError(pos, method, e, self.input.clone()))
+    }
+
+    /// Checks a character index (or count) lies within a string of the given length
+    fn char_index(&self, index: Num, length: usize, pos: Position) -> Result<usize, RuntimeError> {
+        match usize::try_from(index) {
+            Ok(x) if x <= length => Ok(x),
+            _ => Err(RuntimeError::StringIndexOutOfRange(
+                pos,
+                index,
+                length,
+                self.input.clone(),
+            )),
         }
+    }
 
-        let mut input = String::new();
-        if args.len() == 1 {
-            match &args[0] {
-                Node::Primary(x) => {
-                    print!("{}", x);
+    fn expect_arg_count(
+        &self,
+        name: &str,
+        expected: usize,
+        args: &[Value],
+        pos: Position,
+    ) -> Result<(), RuntimeError> {
+        if args.len() == expected {
+            Ok(())
+        } else {
+            Err(RuntimeError::WrongArgumentCount(
+                pos,
+                name.to_string(),
+                expected,
+                args.len(),
+                self.input.clone(),
+            ))
+        }
+    }
+
+    fn expect_integer(
+        &self,
+        name: &str,
+        value: &Value,
+        pos: Position,
+    ) -> Result<Num, RuntimeError> {
+        match value {
+            Value::Number(x) => Ok(*x),
+            _ => Err(RuntimeError::InvalidArgument(
+                pos,
+                name.to_string(),
+                "integer",
+                value.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    fn get_array_ref(&mut self, node: Node) -> Result<Value, RuntimeError> {
+        info!("Getting array reference: {:?}", node);
+        let (ident, indices, pos) = match node {
+            Node::ArrayRef {
+                ident,
+                indices,
+                pos,
+            } => (ident, indices, pos),
+            _ => panic!("Not an array ref"),
+        };
+
+        let indices = self.eval_indices(&ident, indices, pos)?;
+        self.get_index(&ident, &indices, pos)
+    }
+
+    /// Gets an element of an array or character of a string, given one index per dimension
+    fn get_index(
+        &mut self,
+        ident: &str,
+        indices: &[Num],
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        info!("Array Indices: {:?}", indices);
+
+        let value = self.get_variable(ident.to_string(), pos)?;
+        self.get_element(ident, value, indices, pos)
+    }
+
+    /// Finds the element of `value` by walking down one dimension per index
+    fn get_element(
+        &self,
+        ident: &str,
+        mut value: Value,
+        indices: &[Num],
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        for index in indices.iter().copied() {
+            value = match value {
+                Value::Array(mut x) => {
+                    let i = self.array_index(ident, index, x.len(), pos)?;
+                    x.swap_remove(i)
                 }
-                Node::BinaryExpr { .. } => {
-                    let expr = self.run_expr(args[0].clone());
-                    print!("{}", expr);
+                // strings can be indexed by character, but not assigned to
+                Value::String(x) => {
+                    let chars: Vec<char> = x.chars().collect();
+                    match usize::try_from(index) {
+                        Ok(i) if i < chars.len() => Value::String(chars[i].to_string()),
+                        _ => {
+                            return Err(RuntimeError::StringIndexOutOfRange(
+                                pos,
+                                index,
+                                chars.len(),
+                                self.input.clone(),
+                            ))
+                        }
+                    }
                 }
-                Node::VariableRef(x) => {
-                    let var = self.symbol_table.get_variable(x.to_string());
-                    print!("{}", var);
+                x => {
+                    return Err(RuntimeError::NotIndexable(
+                        pos,
+                        x.type_name(),
+                        self.input.clone(),
+                    ))
                 }
-                _ => unimplemented!("cannot print {:?}", args[0]),
-            }
+            };
+        }
+        Ok(value)
+    }
+
+    fn concat(&mut self, lvalue: Value, rvalue: Value) -> Value {
+        Value::String(format!("{}{}", lvalue, rvalue))
+    }
+
+    /// Evaluates the condition of an `if`, `elseif`, `while` or `until` at `pos`
+    fn evaluate_condition(
+        &mut self,
+        expr: Node,
+        keyword: &'static str,
+        pos: Position,
+    ) -> Result<bool, RuntimeError> {
+        let value = self.get_expr_val(expr)?;
+        self.expect_boolean(keyword, value, pos)
+    }
+
+    /// Prints every argument on one line, separated by spaces
+    fn builtin_print(&mut self, args: Vec<Node>) -> Result<(), RuntimeError> {
+        info!("Function was built-in: print");
+        let values = args
+            .into_iter()
+            .map(|x| self.get_expr_val(x).map(|x| x.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        println!("{}", values.join(" "));
+        Ok(())
+    }
+
+    fn builtin_input(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: input");
+        // the optional argument is a prompt, shown on the same line as the input
+        if let Some(prompt) = self.builtin_optional_arg("input", args, pos)? {
+            print!("{}", prompt);
             let _ = io::stdout().flush();
         }
+
+        let mut input = String::new();
         io::stdin()
             .read_line(&mut input)
             .expect("Error reading from STDIN");
         input.pop(); // consume newline
-        Value::String(input)
+        Ok(Value::String(input))
     }
 
-    fn builtin_casti(&mut self, args: Vec<Node>) -> Value {
-        info!("FUnction was built-in: int");
+    /// Evaluates the arguments of a built-in that takes zero or one arguments
+    fn builtin_optional_arg(
+        &mut self,
+        name: &str,
+        args: Vec<Node>,
+        pos: Position,
+    ) -> Result<Option<Value>, RuntimeError> {
         if args.len() > 1 {
-            panic!("int cannot accept more than 1 arg");
+            return Err(RuntimeError::TooManyArguments(
+                pos,
+                name.to_string(),
+                1,
+                args.len(),
+                self.input.clone(),
+            ));
         }
+        args.into_iter()
+            .next()
+            .map(|x| self.get_expr_val(x))
+            .transpose()
+    }
 
-        match &args[0] {
-            Node::Primary(Value::String(x)) => {
-                info!("Casting primary ({}) to int", x);
+    fn builtin_casti(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: int");
+        let value = self.builtin_args("int", 1, args, pos)?.remove(0);
 
-                Value::Number(x.parse().unwrap())
-            }
-            Node::VariableRef(x) => {
-                let var = self.symbol_table.get_variable(x.to_string());
-                info!("Casting variable ({} = {}) to int", x, var);
-                match var {
-                    Value::String(x) => Value::Number(x.parse().unwrap()),
-                    _ => panic!("Invalid variable type for cast"),
-                }
-            }
-            Node::FuncCall { .. } => {
-                info!("Casting result from func call to int");
-                let ret = self.run_func(args[0].clone()).unwrap();
-                info!("Got {ret} from func");
-                match ret {
-                    Value::String(x) => Value::Number(x.parse().unwrap()),
-                    _ => panic!("Invalid variable type for cast"),
-                }
+        info!("Casting {} to int", value);
+        let result = match &value {
+            Value::String(x) => x.trim().parse().ok(),
+            Value::Number(x) => Some(*x),
+            // casting a real to an integer truncates it, as long as it fits
+            Value::Real(x) if x.is_finite() && x.trunc().abs() < Num::MAX as f64 => Some(*x as Num),
+            Value::Boolean(x) => Some(*x as Num),
+            _ => None,
+        };
+        match result {
+            Some(x) => Ok(Value::Number(x)),
+            None => Err(self.invalid_conversion("int", &value, "integer", pos)),
+        }
+    }
+
+    fn builtin_castr(
+        &mut self,
+        name: &str,
+        args: Vec<Node>,
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: float / real");
+        let value = self.builtin_args(name, 1, args, pos)?.remove(0);
+
+        info!("Casting {} to real", value);
+        let result = match &value {
+            Value::String(x) => x.trim().parse().ok(),
+            Value::Number(x) => Some(*x as f64),
+            Value::Real(x) => Some(*x),
+            Value::Boolean(x) => Some(*x as Num as f64),
+            _ => None,
+        };
+        match result {
+            Some(x) => Ok(Value::Real(x)),
+            None => Err(self.invalid_conversion(name, &value, "real", pos)),
+        }
+    }
+
+    fn builtin_casts(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: str");
+        let value = self.builtin_args("str", 1, args, pos)?.remove(0);
+
+        info!("Casting {} to string", value);
+        match value {
+            Value::Array(_) => Err(self.invalid_conversion("str", &value, "string", pos)),
+            x => Ok(Value::String(x.to_string())),
+        }
+    }
+
+    fn builtin_castb(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: bool");
+        let value = self.builtin_args("bool", 1, args, pos)?.remove(0);
+
+        info!("Casting {} to boolean", value);
+        let result = match &value {
+            Value::String(x) => match x.trim().to_lowercase().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            // any non zero number is true
+            Value::Number(x) => Some(*x != 0),
+            Value::Real(x) => Some(*x != 0.0),
+            Value::Boolean(x) => Some(*x),
+            _ => None,
+        };
+        match result {
+            Some(x) => Ok(Value::Boolean(x)),
+            None => Err(self.invalid_conversion("bool", &value, "boolean", pos)),
+        }
+    }
+
+    fn invalid_conversion(
+        &self,
+        name: &str,
+        value: &Value,
+        target: &'static str,
+        pos: Position,
+    ) -> RuntimeError {
+        let shown = match value {
+            Value::String(x) => format!("\"{}\"", x),
+            x => x.to_string(),
+        };
+        RuntimeError::InvalidConversion(
+            pos,
+            name.to_string(),
+            shown,
+            value.type_name(),
+            target,
+            self.input.clone(),
+        )
+    }
+
+    /// Evaluates the arguments of a builtin, checking there are the expected number of them
+    fn builtin_args(
+        &mut self,
+        name: &str,
+        expected: usize,
+        args: Vec<Node>,
+        pos: Position,
+    ) -> Result<Vec<Value>, RuntimeError> {
+        let values = args
+            .into_iter()
+            .map(|x| self.get_expr_val(x))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.expect_arg_count(name, expected, &values, pos)?;
+        Ok(values)
+    }
+
+    fn builtin_asc(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: ASC");
+        let values = self.builtin_args("ASC", 1, args, pos)?;
+
+        // ASC only accepts a single character
+        let mut chars = match &values[0] {
+            Value::String(x) => x.chars(),
+            x => {
+                return Err(RuntimeError::InvalidArgument(
+                    pos,
+                    "ASC".to_string(),
+                    "string",
+                    x.type_name(),
+                    self.input.clone(),
+                ))
             }
-            _ => panic!("Invald cast"),
+        };
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(Value::Number(c as Num)),
+            _ => Err(RuntimeError::InvalidArgument(
+                pos,
+                "ASC".to_string(),
+                "single character",
+                "longer or empty string",
+                self.input.clone(),
+            )),
         }
     }
 
-    fn builtin_length(&mut self, ident: String) -> Value {
-        info!("Built in property: length");
+    fn builtin_chr(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: CHR");
+        let values = self.builtin_args("CHR", 1, args, pos)?;
+
+        let code = self.expect_integer("CHR", &values[0], pos)?;
+        match u32::try_from(code).ok().and_then(char::from_u32) {
+            Some(c) => Ok(Value::String(c.to_string())),
+            None => Err(RuntimeError::InvalidCharacterCode(
+                pos,
+                code,
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Resolves a path given to a file built-in against the working directory
+    fn file_path(&self, value: &Value, name: &str, pos: Position) -> Result<PathBuf, RuntimeError> {
+        match value {
+            Value::String(x) => Ok(self.working_dir.join(x)),
+            x => Err(RuntimeError::InvalidArgument(
+                pos,
+                name.to_string(),
+                "string",
+                x.type_name(),
+                self.input.clone(),
+            )),
+        }
+    }
 
-        let vec = match self.symbol_table.get_variable(ident) {
-            Value::Array(x) => x,
-            _ => panic!("Only arrays have the builtin property: length"),
+    fn builtin_open(
+        &mut self,
+        name: &str,
+        args: Vec<Node>,
+        pos: Position,
+    ) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: {}", name);
+        let values = self.builtin_args(name, 1, args, pos)?;
+
+        let path = self.file_path(&values[0], name, pos)?;
+        let file = match name {
+            "openRead" => FileHandle::open_read(&path),
+            _ => FileHandle::open_write(&path),
         };
+        match file {
+            Ok(x) => Ok(Value::File(x)),
+            Err(e) => Err(RuntimeError::FileError(
+                pos,
+                name.to_string(),
+                format!("could not open {}: {}", path.display(), e),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    fn builtin_new_file(&mut self, args: Vec<Node>, pos: Position) -> Result<(), RuntimeError> {
+        info!("Function was built-in: newFile");
+        let values = self.builtin_args("newFile", 1, args, pos)?;
+
+        let path = self.file_path(&values[0], "newFile", pos)?;
+        match fs::File::create(&path) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(RuntimeError::FileError(
+                pos,
+                "newFile".to_string(),
+                format!("could not create {}: {}", path.display(), e),
+                self.input.clone(),
+            )),
+        }
+    }
+
+    /// Picks a random number between min and max inclusive. The result is an
+    /// integer if both bounds are integers, otherwise it is a real.
+    fn builtin_random(&mut self, args: Vec<Node>, pos: Position) -> Result<Value, RuntimeError> {
+        info!("Function was built-in: random");
+        let values = self.builtin_args("random", 2, args, pos)?;
+
+        for value in &values {
+            if !matches!(value, Value::Number(_) | Value::Real(_)) {
+                return Err(RuntimeError::InvalidArgument(
+                    pos,
+                    "random".to_string(),
+                    "integer or real",
+                    value.type_name(),
+                    self.input.clone(),
+                ));
+            }
+        }
+        let (min, max) = (&values[0], &values[1]);
+        let ordered = matches!(
+            self.compare(min, &Op::LessEqual, max, pos)?,
+            Some(Ordering::Less | Ordering::Equal)
+        );
+        if !ordered || !(self.as_real(max) - self.as_real(min)).is_finite() {
+            return Err(RuntimeError::InvalidRandomRange(
+                pos,
+                min.clone(),
+                max.clone(),
+                self.input.clone(),
+            ));
+        }
+        Ok(match (min, max) {
+            (Value::Number(x), Value::Number(y)) => Value::Number(self.rng.gen_range(*x..=*y)),
+            _ => Value::Real(self.rng.gen_range(self.as_real(min)..=self.as_real(max))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    /// Lexes, parses and runs a whole program
+    fn run(program: &str) -> Result<(), RuntimeError> {
+        let mut lexer = Lexer::new(program.to_string());
+        lexer.lex().unwrap();
+        let ast = Parser::new(lexer.tokens, program.to_string())
+            .parse()
+            .unwrap();
+        Interpretor::new(Box::new(ast), program.to_string()).run()
+    }
+
+    const CLASSES: &str = "
+class A
+    private secret
+    public procedure new()
+        secret = 1
+    endprocedure
+    public function peek()
+        return secret
+    endfunction
+endclass
+class B inherits A
+    public function leak()
+        return secret
+    endfunction
+    public procedure poke()
+        secret = 5
+    endprocedure
+endclass
+b = new B()
+";
+
+    #[test]
+    fn private_attribute_by_name_in_declaring_class() {
+        assert!(run(&format!("{}x = b.peek()", CLASSES)).is_ok());
+    }
+
+    #[test]
+    fn private_attribute_read_by_name_in_subclass() {
+        assert!(matches!(
+            run(&format!("{}x = b.leak()", CLASSES)),
+            Err(RuntimeError::PrivateMember(_, member, class, _)) if member == "secret" && class == "A"
+        ));
+    }
 
-        Value::Number(vec.len() as Num)
+    #[test]
+    fn private_attribute_assigned_by_name_in_subclass() {
+        assert!(matches!(
+            run(&format!("{}b.poke()", CLASSES)),
+            Err(RuntimeError::PrivateMember(_, member, class, _)) if member == "secret" && class == "A"
+        ));
     }
 }
@@ -5,6 +5,8 @@ pub enum TokenKind {
     Ident(String),
     String(String),
     Number(Num),
+    Real(f64),
+    Boolean(bool),
     Keyword(KeywordKind),
     Symbol(SymbolKind),
 }
@@ -12,14 +14,39 @@ pub enum TokenKind {
 #[derive(Clone, Debug, PartialEq)]
 pub enum KeywordKind {
     Do,
+    Until,
     While,
     EndWhile,
+    For,
+    To,
+    Step,
+    Next,
     If,
     Then,
     Else,
+    ElseIf,
     EndIf,
+    Switch,
+    Case,
+    Default,
+    EndSwitch,
     Break,
     Array,
+    Function,
+    EndFunction,
+    Procedure,
+    EndProcedure,
+    Return,
+    Global,
+    ByVal,
+    ByRef,
+    Class,
+    EndClass,
+    Public,
+    Private,
+    New,
+    Inherits,
+    Super,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -32,13 +59,20 @@ pub enum SymbolKind {
     MinusEqual,
     Multiply,
     Divide,
+    IntDivide,
     Mod,
+    Power,
     // comparison
     DoubleEquals,
+    NotEquals,
     Greater,
     GreaterEquals,
     Less,
     LessEquals,
+    // boolean
+    And,
+    Or,
+    Not,
     // other
     LeftBracket,
     RightBracket,
@@ -46,6 +80,26 @@ pub enum SymbolKind {
     RightSqBracket,
     Quote,
     Dot,
+    Comma,
+    Colon,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommentKind {
+    /// `// comment`, running to the end of the line
+    Line,
+    /// `/* comment */`, which may span multiple lines
+    Block,
+}
+
+/// A comment in the source. Comments are not tokens, but are kept as trivia
+/// so the original source can be reconstructed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comment {
+    pub start: Position,
+    pub kind: CommentKind,
+    /// The comment text, without the `//`, `/*` or `*/` delimiters
+    pub text: String,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -53,11 +107,18 @@ pub struct Token {
     pub start: Position,
     pub len: usize,
     pub kind: TokenKind,
+    /// Comments that appear between the previous token and this one
+    pub comments: Vec<Comment>,
 }
 
 impl Token {
     pub fn new(kind: TokenKind, start: Position, len: usize) -> Self {
-        Self { start, len, kind }
+        Self {
+            start,
+            len,
+            kind,
+            comments: Vec::new(),
+        }
     }
 }
 
@@ -67,6 +128,9 @@ pub struct Lexer {
     input_og: String,
     position: Position,
     pub tokens: Vec<Token>,
+    /// Comments not yet attached to a token. Once lexing has finished, this
+    /// holds any comments after the final token.
+    pub comments: Vec<Comment>,
 }
 
 impl Lexer {
@@ -76,6 +140,7 @@ impl Lexer {
             input_og: input,
             position: Position::new(1, 0),
             tokens: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
@@ -90,6 +155,8 @@ impl Lexer {
                     self.position.line += 1;
                 }
                 '\0' | ' ' => continue,
+                '/' if self.peek_char() == '/' => self.line_comment(),
+                '/' if self.peek_char() == '*' => self.block_comment()?,
                 '=' | '<' | '>' | '+' | '-' | '*' | '/' | '%' => {
                     let peek = self.peek_char();
                     match peek {
@@ -100,18 +167,27 @@ impl Lexer {
                         _ => self.symbol(c, ' '),
                     }
                 }
-                '(' => self.push_symbol(SymbolKind::LeftBracket, self.position, 1),
-                ')' => self.push_symbol(SymbolKind::RightBracket, self.position, 1),
-                '[' => self.push_symbol(SymbolKind::LeftSqBracket, self.position, 1),
-                ']' => self.push_symbol(SymbolKind::RightSqBracket, self.position, 1),
-                '.' => self.push_symbol(SymbolKind::Dot, self.position, 1),
+                // '!' is only valid as part of "!="
+                '!' if self.peek_char() == '=' => {
+                    self.panic_pop();
+                    let start_pos = Position::new(self.position.line, self.position.col - 2);
+                    self.push_symbol(SymbolKind::NotEquals, start_pos, 2);
+                }
+                '^' => self.push_symbol(SymbolKind::Power, self.last_position(), 1),
+                '(' => self.push_symbol(SymbolKind::LeftBracket, self.last_position(), 1),
+                ')' => self.push_symbol(SymbolKind::RightBracket, self.last_position(), 1),
+                '[' => self.push_symbol(SymbolKind::LeftSqBracket, self.last_position(), 1),
+                ']' => self.push_symbol(SymbolKind::RightSqBracket, self.last_position(), 1),
+                '.' => self.push_symbol(SymbolKind::Dot, self.last_position(), 1),
+                ',' => self.push_symbol(SymbolKind::Comma, self.last_position(), 1),
+                ':' => self.push_symbol(SymbolKind::Colon, self.last_position(), 1),
                 '"' => self.string(),
-                '0'..='9' => self.numeric(c),
+                '0'..='9' => self.numeric(c)?,
                 'a'..='z' | 'A'..='Z' | '_' => self.ident_or_keyword(c),
                 _ => {
                     return Err(LexerError::UnrecognisedCharacter(
                         c,
-                        self.position,
+                        self.last_position(),
                         self.input_og.clone(),
                     ))
                 }
@@ -145,6 +221,55 @@ impl Lexer {
         }
     }
 
+    /// Lexes a `//` comment up to the end of the line. The first '/' is already consumed.
+    fn line_comment(&mut self) {
+        let start_pos = self.last_position();
+        self.panic_pop(); // consume second '/'
+        let mut text = String::new();
+        while !matches!(self.peek_char(), '\n' | '\r' | '\0') {
+            text.push(self.panic_pop());
+        }
+        self.comments.push(Comment {
+            start: start_pos,
+            kind: CommentKind::Line,
+            text,
+        });
+    }
+
+    /// Lexes a `/* */` comment, which can span multiple lines. The '/' is already consumed.
+    fn block_comment(&mut self) -> Result<(), LexerError> {
+        let start_pos = self.last_position();
+        self.panic_pop(); // consume '*'
+        let mut text = String::new();
+        loop {
+            match self.peek_char() {
+                '\0' => {
+                    return Err(LexerError::UnterminatedComment(
+                        start_pos,
+                        self.input_og.clone(),
+                    ))
+                }
+                '*' if self.peek_second_char() == '/' => {
+                    self.panic_pop();
+                    self.panic_pop();
+                    break;
+                }
+                '\n' => {
+                    text.push(self.panic_pop());
+                    self.position.col = 0;
+                    self.position.line += 1;
+                }
+                _ => text.push(self.panic_pop()),
+            }
+        }
+        self.comments.push(Comment {
+            start: start_pos,
+            kind: CommentKind::Block,
+            text,
+        });
+        Ok(())
+    }
+
     fn string(&mut self) {
         let mut string = String::new();
         let start_pos = Position::new(self.position.line, self.position.col - 1);
@@ -157,21 +282,41 @@ impl Lexer {
     }
 
     /// Lexes a multi-digit number, but requires the first digit of the number
-    /// as it is already consumed
-    fn numeric(&mut self, start: char) {
+    /// as it is already consumed. Numbers containing a decimal point are lexed as reals.
+    fn numeric(&mut self, start: char) -> Result<(), LexerError> {
         let mut strnum = String::new();
         let start_pos = Position::new(self.position.line, self.position.col - 1);
         strnum.push(start);
-        while self.peek_char().is_numeric() {
-            // PANIC: Unwrap should be safe as we verify the character is numeric
+        while self.peek_char().is_ascii_digit() {
+            // PANIC: Unwrap should be safe as we verify the character is a digit
             strnum.push(self.panic_pop());
         }
-        // PANIC: I think we should be fine here, as all of the characters in strnum
-        //          should be verified as being numeric
-        let number = strnum
-            .parse::<Num>()
-            .expect(format!("strnum is not a number! strnum: {}", strnum).as_str());
+        // a '.' is only part of the number if it is followed by another digit
+        if self.peek_char() == '.' && self.peek_second_char().is_ascii_digit() {
+            strnum.push(self.panic_pop());
+            while self.peek_char().is_ascii_digit() {
+                strnum.push(self.panic_pop());
+            }
+            // PANIC: strnum only contains digits and a single '.'
+            let real = strnum
+                .parse::<f64>()
+                .unwrap_or_else(|_| panic!("strnum is not a real! strnum: {}", strnum));
+            self.push_real(real, start_pos, strnum.len());
+            return Ok(());
+        }
+        // strnum only contains digits, so parsing can only fail if it doesn't fit
+        let number = match strnum.parse::<Num>() {
+            Ok(x) => x,
+            Err(_) => {
+                return Err(LexerError::NumberTooLarge(
+                    strnum,
+                    start_pos,
+                    self.input_og.clone(),
+                ))
+            }
+        };
         self.push_number(number, start_pos, strnum.len());
+        Ok(())
     }
 
     fn ident_or_keyword(&mut self, first: char) {
@@ -184,44 +329,89 @@ impl Lexer {
         }
         match ident.as_str() {
             "do" => self.push_keyword(KeywordKind::Do, start_pos, 2),
+            "until" => self.push_keyword(KeywordKind::Until, start_pos, 5),
             "while" => self.push_keyword(KeywordKind::While, start_pos, 5),
             "endwhile" => self.push_keyword(KeywordKind::EndWhile, start_pos, 8),
+            "for" => self.push_keyword(KeywordKind::For, start_pos, 3),
+            "to" => self.push_keyword(KeywordKind::To, start_pos, 2),
+            "step" => self.push_keyword(KeywordKind::Step, start_pos, 4),
+            "next" => self.push_keyword(KeywordKind::Next, start_pos, 4),
             "if" => self.push_keyword(KeywordKind::If, start_pos, 2),
+            "then" => self.push_keyword(KeywordKind::Then, start_pos, 4),
             "else" => self.push_keyword(KeywordKind::Else, start_pos, 4),
+            "elseif" => self.push_keyword(KeywordKind::ElseIf, start_pos, 6),
             "endif" => self.push_keyword(KeywordKind::EndIf, start_pos, 5),
+            "switch" => self.push_keyword(KeywordKind::Switch, start_pos, 6),
+            "case" => self.push_keyword(KeywordKind::Case, start_pos, 4),
+            "default" => self.push_keyword(KeywordKind::Default, start_pos, 7),
+            "endswitch" => self.push_keyword(KeywordKind::EndSwitch, start_pos, 9),
             "break" => self.push_keyword(KeywordKind::Break, start_pos, 5),
             "array" => self.push_keyword(KeywordKind::Array, start_pos, 5),
+            "function" => self.push_keyword(KeywordKind::Function, start_pos, 8),
+            "endfunction" => self.push_keyword(KeywordKind::EndFunction, start_pos, 11),
+            "procedure" => self.push_keyword(KeywordKind::Procedure, start_pos, 9),
+            "endprocedure" => self.push_keyword(KeywordKind::EndProcedure, start_pos, 12),
+            "return" => self.push_keyword(KeywordKind::Return, start_pos, 6),
+            "global" => self.push_keyword(KeywordKind::Global, start_pos, 6),
+            "byVal" => self.push_keyword(KeywordKind::ByVal, start_pos, 5),
+            "byRef" => self.push_keyword(KeywordKind::ByRef, start_pos, 5),
+            "class" => self.push_keyword(KeywordKind::Class, start_pos, 5),
+            "endclass" => self.push_keyword(KeywordKind::EndClass, start_pos, 8),
+            "public" => self.push_keyword(KeywordKind::Public, start_pos, 6),
+            "private" => self.push_keyword(KeywordKind::Private, start_pos, 7),
+            "new" => self.push_keyword(KeywordKind::New, start_pos, 3),
+            "inherits" => self.push_keyword(KeywordKind::Inherits, start_pos, 8),
+            "super" => self.push_keyword(KeywordKind::Super, start_pos, 5),
+            "DIV" => self.push_symbol(SymbolKind::IntDivide, start_pos, 3),
+            "MOD" => self.push_symbol(SymbolKind::Mod, start_pos, 3),
+            "AND" | "and" => self.push_symbol(SymbolKind::And, start_pos, 3),
+            "OR" | "or" => self.push_symbol(SymbolKind::Or, start_pos, 2),
+            "NOT" | "not" => self.push_symbol(SymbolKind::Not, start_pos, 3),
+            "true" => self.push_boolean(true, start_pos, 4),
+            "false" => self.push_boolean(false, start_pos, 5),
             _ => self.push_ident(ident, start_pos),
         }
     }
 
+    /// Pushes a token onto our list of tokens, attaching any comments before it
+    fn push_token(&mut self, mut token: Token) {
+        token.comments = std::mem::take(&mut self.comments);
+        self.tokens.push(token);
+    }
+
     /// Pushes a symbol token onto our list of tokens
     fn push_symbol(&mut self, symbol: SymbolKind, start: Position, len: usize) {
-        self.tokens
-            .push(Token::new(TokenKind::Symbol(symbol), start, len));
+        self.push_token(Token::new(TokenKind::Symbol(symbol), start, len));
     }
 
     /// Pushes a string token onto our list of tokens
     fn push_string(&mut self, string: String, start: Position, len: usize) {
-        self.tokens
-            .push(Token::new(TokenKind::String(string), start, len));
+        self.push_token(Token::new(TokenKind::String(string), start, len));
     }
 
     /// Pushes a number token onto our list of tokens
     fn push_number(&mut self, number: Num, start: Position, len: usize) {
-        self.tokens
-            .push(Token::new(TokenKind::Number(number), start, len));
+        self.push_token(Token::new(TokenKind::Number(number), start, len));
+    }
+
+    /// Pushes a real token onto our list of tokens
+    fn push_real(&mut self, real: f64, start: Position, len: usize) {
+        self.push_token(Token::new(TokenKind::Real(real), start, len));
+    }
+
+    /// Pushes a boolean token onto our list of tokens
+    fn push_boolean(&mut self, boolean: bool, start: Position, len: usize) {
+        self.push_token(Token::new(TokenKind::Boolean(boolean), start, len));
     }
 
     /// Pushes a keyword token onto our list of tokens
     fn push_keyword(&mut self, keyword: KeywordKind, start: Position, len: usize) {
-        self.tokens
-            .push(Token::new(TokenKind::Keyword(keyword), start, len));
+        self.push_token(Token::new(TokenKind::Keyword(keyword), start, len));
     }
 
     /// Pushes an indentifier token onto our list of tokens
     fn push_ident(&mut self, ident: String, start: Position) {
-        self.tokens.push(Token::new(
+        self.push_token(Token::new(
             TokenKind::Ident(ident.clone()),
             start,
             ident.len(),
@@ -231,10 +421,18 @@ impl Lexer {
     /// Peeks the next character
     /// WARN: Returns a null byte if the character doesn't exist.
     fn peek_char(&self) -> char {
-        match self.input.clone().pop() {
-            Some(x) => x,
-            None => '\0',
-        }
+        self.input.clone().pop().unwrap_or('\0')
+    }
+
+    /// Peeks the character after the next character
+    /// WARN: Returns a null byte if the character doesn't exist.
+    fn peek_second_char(&self) -> char {
+        self.input.chars().rev().nth(1).unwrap_or('\0')
+    }
+
+    /// The position of the character that was most recently popped
+    fn last_position(&self) -> Position {
+        Position::new(self.position.line, self.position.col - 1)
     }
 
     fn panic_pop(&mut self) -> char {
@@ -268,6 +466,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn not_equals() {
+        let mut lexer = Lexer::new("!= ==".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Symbol(SymbolKind::NotEquals),
+                TokenKind::Symbol(SymbolKind::DoubleEquals)
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_exclamation() {
+        let mut lexer = Lexer::new("x ! y".to_string());
+        assert!(matches!(
+            lexer.lex(),
+            Err(LexerError::UnrecognisedCharacter('!', _, _))
+        ));
+    }
+
     #[test]
     fn greater() {
         let mut lexer = Lexer::new(">= >".to_string());
@@ -343,6 +563,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn word_arithmetic() {
+        let mut lexer = Lexer::new("7 DIV 2 MOD 3 ^ 2".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Number(7),
+                TokenKind::Symbol(SymbolKind::IntDivide),
+                TokenKind::Number(2),
+                TokenKind::Symbol(SymbolKind::Mod),
+                TokenKind::Number(3),
+                TokenKind::Symbol(SymbolKind::Power),
+                TokenKind::Number(2),
+            ]
+        );
+    }
+
     #[test]
     fn string() {
         let mut lexer = Lexer::new("\"this is a test string\" + 7".to_string());
@@ -364,6 +602,30 @@ mod tests {
         assert_eq!(lexer.token_kinds(), vec![TokenKind::Number(325)])
     }
 
+    #[test]
+    fn real() {
+        let mut lexer = Lexer::new("2.5 10.0".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![TokenKind::Real(2.5), TokenKind::Real(10.0)]
+        )
+    }
+
+    #[test]
+    fn numeric_dot() {
+        let mut lexer = Lexer::new("3.length".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Number(3),
+                TokenKind::Symbol(SymbolKind::Dot),
+                TokenKind::Ident("length".to_string()),
+            ]
+        )
+    }
+
     #[test]
     fn multi_numeric() {
         let mut lexer = Lexer::new("100 27".to_string());
@@ -388,6 +650,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn boolean_operators() {
+        let mut lexer = Lexer::new("a AND b or NOT true and false".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Ident("a".to_string()),
+                TokenKind::Symbol(SymbolKind::And),
+                TokenKind::Ident("b".to_string()),
+                TokenKind::Symbol(SymbolKind::Or),
+                TokenKind::Symbol(SymbolKind::Not),
+                TokenKind::Boolean(true),
+                TokenKind::Symbol(SymbolKind::And),
+                TokenKind::Boolean(false),
+            ]
+        )
+    }
+
     #[test]
     fn keyword_while() {
         let mut lexer = Lexer::new("do while break endwhile".to_string());
@@ -403,20 +684,125 @@ mod tests {
         )
     }
 
+    #[test]
+    fn keyword_do_until() {
+        let mut lexer = Lexer::new("do until".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Keyword(KeywordKind::Do),
+                TokenKind::Keyword(KeywordKind::Until),
+            ]
+        )
+    }
+
+    #[test]
+    fn keyword_for() {
+        let mut lexer = Lexer::new("for to step next".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Keyword(KeywordKind::For),
+                TokenKind::Keyword(KeywordKind::To),
+                TokenKind::Keyword(KeywordKind::Step),
+                TokenKind::Keyword(KeywordKind::Next),
+            ]
+        )
+    }
+
     #[test]
     fn keyword_if() {
-        let mut lexer = Lexer::new("if else endif".to_string());
+        let mut lexer = Lexer::new("if then elseif else endif".to_string());
         let _ = lexer.lex();
         assert_eq!(
             lexer.token_kinds(),
             vec![
                 TokenKind::Keyword(KeywordKind::If),
+                TokenKind::Keyword(KeywordKind::Then),
+                TokenKind::Keyword(KeywordKind::ElseIf),
                 TokenKind::Keyword(KeywordKind::Else),
                 TokenKind::Keyword(KeywordKind::EndIf),
             ]
         )
     }
 
+    #[test]
+    fn keyword_switch() {
+        let mut lexer = Lexer::new("switch x: case 1: default: endswitch".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Keyword(KeywordKind::Switch),
+                TokenKind::Ident("x".to_string()),
+                TokenKind::Symbol(SymbolKind::Colon),
+                TokenKind::Keyword(KeywordKind::Case),
+                TokenKind::Number(1),
+                TokenKind::Symbol(SymbolKind::Colon),
+                TokenKind::Keyword(KeywordKind::Default),
+                TokenKind::Symbol(SymbolKind::Colon),
+                TokenKind::Keyword(KeywordKind::EndSwitch),
+            ]
+        )
+    }
+
+    #[test]
+    fn keyword_subroutine() {
+        let mut lexer = Lexer::new(
+            "function endfunction procedure endprocedure return global byVal byRef".to_string(),
+        );
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Keyword(KeywordKind::Function),
+                TokenKind::Keyword(KeywordKind::EndFunction),
+                TokenKind::Keyword(KeywordKind::Procedure),
+                TokenKind::Keyword(KeywordKind::EndProcedure),
+                TokenKind::Keyword(KeywordKind::Return),
+                TokenKind::Keyword(KeywordKind::Global),
+                TokenKind::Keyword(KeywordKind::ByVal),
+                TokenKind::Keyword(KeywordKind::ByRef),
+            ]
+        )
+    }
+
+    #[test]
+    fn keyword_class() {
+        let mut lexer = Lexer::new("class inherits public private new super endclass".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Keyword(KeywordKind::Class),
+                TokenKind::Keyword(KeywordKind::Inherits),
+                TokenKind::Keyword(KeywordKind::Public),
+                TokenKind::Keyword(KeywordKind::Private),
+                TokenKind::Keyword(KeywordKind::New),
+                TokenKind::Keyword(KeywordKind::Super),
+                TokenKind::Keyword(KeywordKind::EndClass),
+            ]
+        )
+    }
+
+    #[test]
+    fn comma() {
+        let mut lexer = Lexer::new("(a, b)".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Symbol(SymbolKind::LeftBracket),
+                TokenKind::Ident("a".to_string()),
+                TokenKind::Symbol(SymbolKind::Comma),
+                TokenKind::Ident("b".to_string()),
+                TokenKind::Symbol(SymbolKind::RightBracket),
+            ]
+        )
+    }
+
     #[test]
     fn ident() {
         let mut lexer = Lexer::new("apples".to_string());
@@ -440,4 +826,77 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn line_comment() {
+        let mut lexer = Lexer::new("x = 8 // 2\n// trailing".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Ident("x".to_string()),
+                TokenKind::Symbol(SymbolKind::Equals),
+                TokenKind::Number(8),
+            ]
+        );
+        assert_eq!(
+            lexer.comments,
+            vec![
+                Comment {
+                    start: Position::new(1, 6),
+                    kind: CommentKind::Line,
+                    text: " 2".to_string(),
+                },
+                Comment {
+                    start: Position::new(2, 0),
+                    kind: CommentKind::Line,
+                    text: " trailing".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn block_comment() {
+        let mut lexer = Lexer::new("/* a\nb */ x /**/ 8 / 2".to_string());
+        let _ = lexer.lex();
+        assert_eq!(
+            lexer.token_kinds(),
+            vec![
+                TokenKind::Ident("x".to_string()),
+                TokenKind::Number(8),
+                TokenKind::Symbol(SymbolKind::Divide),
+                TokenKind::Number(2),
+            ]
+        );
+        assert_eq!(lexer.tokens[0].start, Position::new(2, 5));
+        assert_eq!(
+            lexer.tokens[0].comments,
+            vec![Comment {
+                start: Position::new(1, 0),
+                kind: CommentKind::Block,
+                text: " a\nb ".to_string(),
+            }]
+        );
+        assert_eq!(lexer.tokens[1].comments[0].text, "");
+        assert!(lexer.comments.is_empty());
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        let mut lexer = Lexer::new("x /* never closed".to_string());
+        assert!(matches!(
+            lexer.lex(),
+            Err(LexerError::UnterminatedComment(_, _))
+        ));
+    }
+
+    #[test]
+    fn number_too_large() {
+        let mut lexer = Lexer::new("9223372036854775807 9223372036854775808".to_string());
+        assert!(matches!(
+            lexer.lex(),
+            Err(LexerError::NumberTooLarge(x, _, _)) if x == "9223372036854775808"
+        ));
+    }
 }
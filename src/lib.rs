@@ -1,24 +1,49 @@
 use std::fmt::Display;
 
+use file::FileHandle;
 use lexer::TokenKind;
+use object::ObjectRef;
 
-pub type Num = u64;
+pub type Num = i64;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Number(Num),
+    Real(f64),
     String(String),
     Boolean(bool),
     Array(Vec<Value>),
+    File(FileHandle),
+    Object(ObjectRef),
+}
+
+impl Value {
+    /// The name of the value's type, as used in error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "integer",
+            Self::Real(_) => "real",
+            Self::String(_) => "string",
+            Self::Boolean(_) => "boolean",
+            Self::Array(_) => "array",
+            Self::File(_) => "file",
+            Self::Object(_) => "object",
+        }
+    }
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Number(x) => write!(f, "{}", x),
+            // reals always display with a decimal point, so 3.0 is not shown as 3
+            Self::Real(x) if x.is_finite() && x.fract() == 0.0 => write!(f, "{:.1}", x),
+            Self::Real(x) => write!(f, "{}", x),
             Self::String(x) => write!(f, "{}", x),
             Self::Boolean(x) => write!(f, "{}", x),
             Self::Array(x) => write!(f, "{:?}", x),
+            Self::File(x) => write!(f, "<file {}>", x.path().display()),
+            Self::Object(x) => write!(f, "<{} object>", x.class()),
         }
     }
 }
@@ -29,12 +54,18 @@ pub enum Op {
     Minus,
     Multiply,
     Divide,
+    IntDivide,
     Mod,
+    Power,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
     EqualTo,
+    NotEqualTo,
+    And,
+    Or,
+    Not,
 }
 
 impl From<TokenKind> for Op {
@@ -42,16 +73,49 @@ impl From<TokenKind> for Op {
         match kind {
             TokenKind::Symbol(lexer::SymbolKind::Plus) => Op::Plus,
             TokenKind::Symbol(lexer::SymbolKind::Minus) => Op::Minus,
+            TokenKind::Symbol(lexer::SymbolKind::Multiply) => Op::Multiply,
+            TokenKind::Symbol(lexer::SymbolKind::Divide) => Op::Divide,
+            TokenKind::Symbol(lexer::SymbolKind::IntDivide) => Op::IntDivide,
+            TokenKind::Symbol(lexer::SymbolKind::Mod) => Op::Mod,
+            TokenKind::Symbol(lexer::SymbolKind::Power) => Op::Power,
             TokenKind::Symbol(lexer::SymbolKind::Greater) => Op::Greater,
             TokenKind::Symbol(lexer::SymbolKind::GreaterEquals) => Op::GreaterEqual,
             TokenKind::Symbol(lexer::SymbolKind::Less) => Op::Less,
             TokenKind::Symbol(lexer::SymbolKind::LessEquals) => Op::LessEqual,
             TokenKind::Symbol(lexer::SymbolKind::DoubleEquals) => Op::EqualTo,
+            TokenKind::Symbol(lexer::SymbolKind::NotEquals) => Op::NotEqualTo,
+            TokenKind::Symbol(lexer::SymbolKind::And) => Op::And,
+            TokenKind::Symbol(lexer::SymbolKind::Or) => Op::Or,
+            TokenKind::Symbol(lexer::SymbolKind::Not) => Op::Not,
             _ => panic!("Cannot create Operator from Token: {:?}", kind),
         }
     }
 }
 
+impl Op {
+    /// How the operator is written in OCR, for error messages
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Op::Plus => "+",
+            Op::Minus => "-",
+            Op::Multiply => "*",
+            Op::Divide => "/",
+            Op::IntDivide => "DIV",
+            Op::Mod => "MOD",
+            Op::Power => "^",
+            Op::Greater => ">",
+            Op::GreaterEqual => ">=",
+            Op::Less => "<",
+            Op::LessEqual => "<=",
+            Op::EqualTo => "==",
+            Op::NotEqualTo => "!=",
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Position {
     pub line: usize,
@@ -72,7 +136,9 @@ impl Display for Position {
 
 pub mod ast;
 pub mod error;
+pub mod file;
 pub mod interpretor;
 pub mod lexer;
+pub mod object;
 pub mod parser;
 pub mod symbol_table;
@@ -0,0 +1,52 @@
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+    fmt::Debug,
+    rc::Rc,
+};
+
+use crate::Value;
+
+/// An instance of a class, as created by `new`
+#[derive(Debug)]
+pub struct Object {
+    pub class: String,
+    pub attributes: HashMap<String, Value>,
+}
+
+/// A reference to an object. Objects are shared rather than copied, so
+/// assigning an object to another variable refers to the same object.
+#[derive(Clone)]
+pub struct ObjectRef(Rc<RefCell<Object>>);
+
+impl ObjectRef {
+    pub fn new(class: String, attributes: HashMap<String, Value>) -> Self {
+        Self(Rc::new(RefCell::new(Object { class, attributes })))
+    }
+
+    pub fn borrow(&self) -> Ref<'_, Object> {
+        self.0.borrow()
+    }
+
+    pub fn borrow_mut(&self) -> RefMut<'_, Object> {
+        self.0.borrow_mut()
+    }
+
+    pub fn class(&self) -> String {
+        self.0.borrow().class.clone()
+    }
+}
+
+impl PartialEq for ObjectRef {
+    /// Two references are only equal if they refer to the same object
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for ObjectRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // attributes aren't shown, as an object may refer back to itself
+        write!(f, "ObjectRef({})", self.0.borrow().class)
+    }
+}
@@ -1,12 +1,35 @@
 use log::{info, warn};
 
 use crate::{
-    ast::Node,
+    ast::{Node, Param, Passing, Visibility},
     error::ParserError,
     lexer::{KeywordKind, SymbolKind, Token, TokenKind},
     Op, Value,
 };
 
+/// Binding strength of binary operators, from loosest to tightest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Or,
+    And,
+    Comparison,
+    Sum,
+    Product,
+    Power,
+}
+
+impl Precedence {
+    fn next(self) -> Self {
+        match self {
+            Precedence::Or => Precedence::And,
+            Precedence::And => Precedence::Comparison,
+            Precedence::Comparison => Precedence::Sum,
+            Precedence::Sum => Precedence::Product,
+            Precedence::Product | Precedence::Power => Precedence::Power,
+        }
+    }
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     input: String,
@@ -47,69 +70,209 @@ impl Parser {
                         TokenKind::Symbol(SymbolKind::LeftBracket) => {
                             nodes.push(self.parse_func_call());
                         }
-                        TokenKind::Symbol(SymbolKind::LeftSqBracket) => {
-                            nodes.push(self.parse_array_assign_ind());
-                        }
-                        TokenKind::Symbol(SymbolKind::Dot) => {
-                            nodes.push(self.parse_dot_expr());
+                        TokenKind::Symbol(SymbolKind::LeftSqBracket)
+                        | TokenKind::Symbol(SymbolKind::Dot) => {
+                            nodes.push(self.parse_dot_statement()?);
                         }
                         _ => unimplemented!("unimplemented ident"),
                     }
                 }
                 TokenKind::Keyword(KeywordKind::Array) => {
-                    nodes.push(self.parse_array());
+                    nodes.push(self.parse_array()?);
                 }
                 TokenKind::Keyword(KeywordKind::If) => {
-                    nodes.push(self.parse_if());
+                    nodes.push(self.parse_if()?);
+                }
+                TokenKind::Keyword(KeywordKind::Switch) => {
+                    nodes.push(self.parse_switch()?);
                 }
                 TokenKind::Keyword(KeywordKind::While) => {
-                    nodes.push(self.parse_while());
+                    nodes.push(self.parse_while()?);
                 }
-                TokenKind::Keyword(KeywordKind::EndIf)
-                | TokenKind::Keyword(KeywordKind::EndWhile) => {
+                TokenKind::Keyword(KeywordKind::Do) => {
+                    nodes.push(self.parse_do_until()?);
+                }
+                TokenKind::Keyword(KeywordKind::For) => {
+                    nodes.push(self.parse_for()?);
+                }
+                TokenKind::Keyword(KeywordKind::Function)
+                | TokenKind::Keyword(KeywordKind::Procedure) => {
+                    nodes.push(self.parse_subroutine()?);
+                }
+                TokenKind::Keyword(KeywordKind::Return) => {
+                    nodes.push(self.parse_return());
+                }
+                TokenKind::Keyword(KeywordKind::Global) => {
+                    nodes.push(self.parse_global());
+                }
+                TokenKind::Keyword(KeywordKind::Class) => {
+                    nodes.push(self.parse_class()?);
+                }
+                TokenKind::Keyword(KeywordKind::Super) => {
+                    nodes.push(self.parse_dot_statement()?);
+                }
+                ref kind if ends_block(kind) => {
                     warn!("return from block");
                     return Ok(Node::Block(nodes));
                 }
-                TokenKind::Keyword(KeywordKind::Else) => {
-                    return Ok(Node::Block(nodes));
+                _ => {
+                    return Err(ParserError::InvalidTokenInBlock(
+                        Box::new(token),
+                        self.input.clone(),
+                    ))
                 }
-                _ => return Err(ParserError::InvalidTokenInBlock(token, self.input.clone())),
             }
         }
         Ok(Node::Block(nodes))
     }
 
-    fn parse_if(&mut self) -> Node {
+    fn parse_if(&mut self) -> Result<Node, ParserError> {
         info!("Parsing if statement");
 
-        self.get_token(); // consume "if"
+        let pos = self.get_token().start; // consume "if"
         let expr = self.parse_cond();
         self.get_token(); // consume "then"
-        let then = self.parse_block().unwrap();
-        let els = match self.get_token().kind {
-            TokenKind::Keyword(KeywordKind::Else) => self.parse_block().unwrap(),
-            _ => Node::Block(Vec::new()),
-        };
+        let then = self.parse_block()?;
+
+        let mut elseifs = Vec::new();
+        let mut els = Node::Block(Vec::new());
+        loop {
+            let token = self.get_token();
+            match token.kind {
+                TokenKind::Keyword(KeywordKind::ElseIf) => {
+                    let expr = self.parse_cond();
+                    self.get_token(); // consume "then"
+                    elseifs.push((expr, self.parse_block()?, token.start));
+                }
+                TokenKind::Keyword(KeywordKind::Else) => {
+                    els = self.parse_block()?;
+                    self.get_token(); // consume "endif"
+                    break;
+                }
+                _ => break,
+            }
+        }
 
-        Node::IfExpr {
+        Ok(Node::IfExpr {
             expr: Box::new(expr),
             then: Box::new(then),
+            elseifs,
             els: Box::new(els),
+            pos,
+        })
+    }
+
+    fn parse_switch(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing switch statement");
+
+        self.get_token(); // consume "switch"
+        let expr = self.parse_cond();
+        self.get_token(); // consume ':'
+
+        let mut cases = Vec::new();
+        let mut default = None;
+        loop {
+            let token = self.get_token();
+            match token.kind {
+                TokenKind::Keyword(KeywordKind::Case) => {
+                    let value = self.parse_cond();
+                    self.get_token(); // consume ':'
+                    cases.push((value, self.parse_block()?));
+                }
+                TokenKind::Keyword(KeywordKind::Default) => {
+                    self.get_token(); // consume ':'
+                    default = Some(Box::new(self.parse_block()?));
+                }
+                TokenKind::Keyword(KeywordKind::EndSwitch) => break,
+                _ => {
+                    return Err(ParserError::InvalidTokenInSwitch(
+                        Box::new(token),
+                        self.input.clone(),
+                    ))
+                }
+            }
         }
+
+        Ok(Node::Switch {
+            expr: Box::new(expr),
+            cases,
+            default,
+        })
     }
 
-    fn parse_while(&mut self) -> Node {
+    fn parse_while(&mut self) -> Result<Node, ParserError> {
         info!("Parsing while statement");
 
-        self.get_token(); // consume "while"
+        let pos = self.get_token().start; // consume "while"
         let expr = self.parse_cond();
-        let body = self.parse_block().unwrap();
+        let body = self.parse_block()?;
         self.get_token(); // consume "endwhile"
 
-        Node::WhileStmt {
+        Ok(Node::WhileStmt {
             expr: Box::new(expr),
             body: Box::new(body),
+            pos,
+        })
+    }
+
+    fn parse_do_until(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing do until statement");
+
+        self.get_token(); // consume "do"
+        let body = self.parse_block()?;
+        let pos = self.get_token().start; // consume "until"
+        let expr = self.parse_cond();
+
+        Ok(Node::DoUntil {
+            body: Box::new(body),
+            expr: Box::new(expr),
+            pos,
+        })
+    }
+
+    fn parse_for(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing for loop");
+
+        let pos = self.get_token().start; // consume "for"
+        let ident = match self.get_token().kind {
+            TokenKind::Ident(x) => x,
+            _ => panic!("for loop must have a loop variable"),
+        };
+        self.get_token(); // consume '='
+        let start = self.parse_cond();
+        self.get_token(); // consume "to"
+        let end = self.parse_cond();
+
+        let mut step = None;
+        if let Some(TokenKind::Keyword(KeywordKind::Step)) = self.peek_token().map(|x| x.kind) {
+            self.get_token(); // consume "step"
+            step = Some(Box::new(self.parse_cond()));
+        }
+
+        let body = self.parse_block()?;
+        self.get_token(); // consume "next"
+
+        // the variable after "next" must match the loop variable
+        let next = self.get_token();
+        match &next.kind {
+            TokenKind::Ident(x) if *x == ident => (),
+            _ => {
+                return Err(ParserError::MismatchedNextVariable(
+                    Box::new(next),
+                    ident,
+                    self.input.clone(),
+                ))
+            }
         }
+
+        Ok(Node::ForStmt {
+            ident,
+            start: Box::new(start),
+            end: Box::new(end),
+            step,
+            body: Box::new(body),
+            pos,
+        })
     }
 
     fn parse_func_call(&mut self) -> Node {
@@ -120,199 +283,460 @@ impl Parser {
             TokenKind::Ident(x) => x,
             _ => panic!("assignment must start with ident!"),
         };
-        // TODO: parse arguments
+        let args = self.parse_args();
+        Node::FuncCall {
+            ident,
+            args,
+            pos: token.start,
+        }
+    }
+
+    /// Parses a bracketed, comma separated argument list
+    fn parse_args(&mut self) -> Vec<Node> {
         let mut args = Vec::new();
         match self.get_token().kind {
             TokenKind::Symbol(SymbolKind::LeftBracket) => {
                 match self.peek_token().unwrap().kind {
                     TokenKind::Symbol(SymbolKind::RightBracket) => (),
-                    _ => args.push(self.parse_arg()), // TODO: Multiple args, string args
+                    _ => {
+                        args.push(self.parse_arg());
+                        while let Some(TokenKind::Symbol(SymbolKind::Comma)) =
+                            self.peek_token().map(|x| x.kind)
+                        {
+                            self.get_token(); // consume ','
+                            args.push(self.parse_arg());
+                        }
+                    }
                 }
             }
             _ => panic!("Must have bracket after function!"),
         };
         self.get_token(); // consume final bracket
-        Node::FuncCall { ident, args }
+        args
     }
 
     fn parse_arg(&mut self) -> Node {
         info!("Parsing an argument");
 
-        match self.peek_token().unwrap().kind {
-            _ => self.parse_cond(),
+        self.parse_cond()
+    }
+
+    /// Parses a `function` or `procedure` declaration, up to and including
+    /// its closing `endfunction` / `endprocedure`
+    fn parse_subroutine(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing subroutine declaration");
+
+        let is_function = match self.get_token().kind {
+            TokenKind::Keyword(KeywordKind::Function) => true,
+            TokenKind::Keyword(KeywordKind::Procedure) => false,
+            _ => panic!("subroutine must start with function or procedure"),
+        };
+        let ident = match self.get_token().kind {
+            TokenKind::Ident(x) => x,
+            // constructors are declared as `procedure new(...)`
+            TokenKind::Keyword(KeywordKind::New) => "new".to_string(),
+            _ => panic!("subroutine must have ident"),
+        };
+
+        let mut params = Vec::new();
+        match self.get_token().kind {
+            TokenKind::Symbol(SymbolKind::LeftBracket) => (),
+            _ => panic!("Must have bracket after subroutine name!"),
+        };
+        // the passing mode can come before the name, `byRef x`, or after it, `x:byRef`
+        let mut passing = Passing::Default;
+        loop {
+            match self.get_token().kind {
+                TokenKind::Ident(ident) => {
+                    params.push(Param { ident, passing });
+                    passing = Passing::Default;
+                }
+                TokenKind::Keyword(KeywordKind::ByVal) => passing = Passing::ByVal,
+                TokenKind::Keyword(KeywordKind::ByRef) => passing = Passing::ByRef,
+                TokenKind::Symbol(SymbolKind::Colon) => {
+                    let param = params
+                        .last_mut()
+                        .expect("Passing mode must follow a parameter");
+                    param.passing = match self.get_token().kind {
+                        TokenKind::Keyword(KeywordKind::ByVal) => Passing::ByVal,
+                        TokenKind::Keyword(KeywordKind::ByRef) => Passing::ByRef,
+                        x => panic!("Expected byVal or byRef after ':', found {:?}", x),
+                    };
+                }
+                TokenKind::Symbol(SymbolKind::Comma) => (),
+                TokenKind::Symbol(SymbolKind::RightBracket) => break,
+                x => panic!("Invalid token in parameter list: {:?}", x),
+            }
+        }
+
+        let body = self.parse_block()?;
+        self.get_token(); // consume "endfunction" / "endprocedure"
+
+        if is_function {
+            Ok(Node::FunctionDecl {
+                ident,
+                params,
+                body: Box::new(body),
+            })
+        } else {
+            Ok(Node::ProcedureDecl {
+                ident,
+                params,
+                body: Box::new(body),
+            })
+        }
+    }
+
+    fn parse_return(&mut self) -> Node {
+        info!("Parsing return");
+
+        let token = self.get_token(); // consume "return"
+
+        // a procedure may return without a value, which is followed by the end
+        // of the block or a statement on the next line
+        let value = match self.peek_token() {
+            Some(next) if !ends_block(&next.kind) && next.start.line == token.start.line => {
+                Some(Box::new(self.parse_cond()))
+            }
+            _ => None,
+        };
+        Node::Return {
+            value,
+            pos: token.start,
         }
     }
 
-    fn parse_assign(&mut self) -> Node {
-        info!("Parsing assign");
+    fn parse_class(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing class declaration");
 
-        let token = self.get_token();
-        let ident = match token.kind {
+        self.get_token(); // consume "class"
+        let ident = match self.get_token().kind {
             TokenKind::Ident(x) => x,
-            _ => panic!("assignment must start with ident!"),
+            _ => panic!("class must have ident"),
         };
-        // TODO: Verify equals
-        self.get_token(); // consume '='
-        let expr = match self.peek_token().unwrap().kind {
-            _ => self.parse_cond(),
+        let parent = match self.peek_token().map(|x| x.kind) {
+            Some(TokenKind::Keyword(KeywordKind::Inherits)) => {
+                self.get_token(); // consume "inherits"
+                let token = self.get_token();
+                match token.kind {
+                    TokenKind::Ident(x) => Some((x, token.start)),
+                    _ => panic!("inherits must be followed by a class name"),
+                }
+            }
+            _ => None,
         };
-        Node::Assign {
+
+        let mut attributes = Vec::new();
+        let mut methods = Vec::new();
+        loop {
+            let token = self.peek_token().unwrap();
+            // members are public unless marked private
+            let visibility = match token.kind {
+                TokenKind::Keyword(KeywordKind::Public) => Visibility::Public,
+                TokenKind::Keyword(KeywordKind::Private) => Visibility::Private,
+                TokenKind::Keyword(KeywordKind::EndClass) => {
+                    self.get_token(); // consume "endclass"
+                    break;
+                }
+                _ => Visibility::Public,
+            };
+            if matches!(
+                token.kind,
+                TokenKind::Keyword(KeywordKind::Public) | TokenKind::Keyword(KeywordKind::Private)
+            ) {
+                self.get_token(); // consume "public" / "private"
+            }
+
+            let token = self.peek_token().unwrap();
+            match token.kind {
+                TokenKind::Keyword(KeywordKind::Function)
+                | TokenKind::Keyword(KeywordKind::Procedure) => {
+                    methods.push((self.parse_subroutine()?, visibility));
+                }
+                TokenKind::Ident(x) => {
+                    self.get_token();
+                    attributes.push((x, visibility));
+                }
+                _ => {
+                    return Err(ParserError::InvalidTokenInClass(
+                        Box::new(token),
+                        self.input.clone(),
+                    ))
+                }
+            }
+        }
+
+        Ok(Node::ClassDecl {
             ident,
-            value: Box::new(expr),
+            parent,
+            attributes,
+            methods,
+        })
+    }
+
+    /// Parses a statement starting with an array element or dot expression,
+    /// such as `pets[i].describe()`. It is either a method call, or an
+    /// assignment to an array element or attribute.
+    fn parse_dot_statement(&mut self) -> Result<Node, ParserError> {
+        info!("Parsing dot statement");
+
+        // PANIC: this is only called when there are tokens left
+        let start = self.peek_token().unwrap();
+        let node = self.parse_factor();
+        match self.peek_token() {
+            Some(token) if token.kind == TokenKind::Symbol(SymbolKind::Equals) => {
+                self.get_token(); // consume '='
+                match node {
+                    Node::DotExpr {
+                        left,
+                        right,
+                        args: None,
+                        pos,
+                    } => Ok(Node::AttributeAssign {
+                        object: left,
+                        ident: right,
+                        value: Box::new(self.parse_cond()),
+                        pos,
+                    }),
+                    Node::ArrayRef {
+                        ident,
+                        indices,
+                        pos,
+                    } => Ok(Node::ArrayAssingIndex {
+                        ident,
+                        indices,
+                        value: Box::new(self.parse_cond()),
+                        pos,
+                    }),
+                    _ => Err(ParserError::InvalidAssignTarget(
+                        Box::new(token),
+                        self.input.clone(),
+                    )),
+                }
+            }
+            _ => match node {
+                Node::DotExpr { .. } | Node::SuperCall { .. } => Ok(node),
+                _ => Err(ParserError::IncompleteStatement(
+                    Box::new(start),
+                    self.input.clone(),
+                )),
+            },
+        }
+    }
+
+    fn parse_global(&mut self) -> Node {
+        info!("Parsing global");
+
+        self.get_token(); // consume "global"
+        match self.parse_assign() {
+            Node::Assign { ident, value, .. } => Node::GlobalDecl { ident, value },
+            _ => unreachable!("parse_assign always returns an assign"),
         }
     }
 
-    fn parse_array_assign_ind(&mut self) -> Node {
-        info!("Parsing assign to array index");
+    fn parse_assign(&mut self) -> Node {
+        info!("Parsing assign");
 
         let token = self.get_token();
         let ident = match token.kind {
             TokenKind::Ident(x) => x,
-            _ => panic!("array assign must have ident"),
-        };
-
-        let index = match self.get_token().kind {
-            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_cond(),
-            _ => panic!("array must be indexed with ["),
-        };
-
-        self.get_token(); // consume '['
-        let value = match self.get_token().kind {
-            TokenKind::Symbol(SymbolKind::Equals) => self.parse_cond(),
-            _ => panic!("Must assign array with ="),
+            _ => panic!("assignment must start with ident!"),
         };
-
-        Node::ArrayAssingIndex {
+        // TODO: Verify equals
+        self.get_token(); // consume '='
+        let expr = self.parse_cond();
+        Node::Assign {
             ident,
-            index: Box::new(index),
-            value: Box::new(value),
+            value: Box::new(expr),
+            pos: token.start,
         }
     }
 
-    fn parse_array(&mut self) -> Node {
+    fn parse_array(&mut self) -> Result<Node, ParserError> {
         info!("Parsing array");
 
         self.get_token(); // consume 'array'
-        let ident = match self.get_token().kind {
-            TokenKind::Ident(x) => x,
+        let token = self.get_token();
+        let ident = match &token.kind {
+            TokenKind::Ident(x) => x.clone(),
             _ => panic!("array must have ident"),
         };
 
-        let size = match self.get_token().kind {
-            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_cond(),
+        let sizes = match self.get_token().kind {
+            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_index_list(),
+            // `array names = [...]` takes its size from the literal
+            TokenKind::Symbol(SymbolKind::Equals) => {
+                return match self.peek_token() {
+                    Some(x) if x.kind == TokenKind::Symbol(SymbolKind::LeftSqBracket) => {
+                        match self.parse_array_literal() {
+                            Node::ArrayLiteral(elements) => {
+                                Ok(Node::ArrayLiteralAssign { ident, elements })
+                            }
+                            _ => unreachable!("parse_array_literal always returns a literal"),
+                        }
+                    }
+                    x => Err(ParserError::ExpectedArrayLiteral(
+                        Box::new(x.unwrap_or(token)),
+                        self.input.clone(),
+                    )),
+                };
+            }
             _ => panic!("array must have ["),
         };
 
-        self.get_token(); // consume final '['
-
-        Node::ArrayAssign {
+        Ok(Node::ArrayAssign {
             ident,
-            size: Box::new(size),
+            sizes,
+            pos: token.start,
+        })
+    }
+
+    /// Parses the comma separated expressions between square brackets, as in
+    /// `board[i, j]`. The opening '[' should already be consumed.
+    fn parse_index_list(&mut self) -> Vec<Node> {
+        let mut indices = vec![self.parse_cond()];
+        while let Some(TokenKind::Symbol(SymbolKind::Comma)) = self.peek_token().map(|x| x.kind) {
+            self.get_token(); // consume ','
+            indices.push(self.parse_cond());
         }
+        self.get_token(); // consume final ']'
+        indices
     }
 
-    fn parse_dot_expr(&mut self) -> Node {
+    /// Parses any `.member` or `.method(args)` suffixes after a value
+    fn parse_dot_expr(&mut self, left: Node) -> Node {
         info!("Parsing dot expr");
 
-        let left = match self.get_token().kind {
-            TokenKind::Ident(x) => x,
-            _ => panic!("Dot expressions only supports idents currently"),
-        };
-
-        self.get_token(); // consume '.'
-        let right = match self.get_token().kind {
-            TokenKind::Ident(x) => x,
-            _ => panic!("Dot expression rvalue must be ident"),
-        };
-
-        Node::DotExpr { left, right }
+        let mut node = left;
+        while let Some(TokenKind::Symbol(SymbolKind::Dot)) = self.peek_token().map(|x| x.kind) {
+            self.get_token(); // consume '.'
+            let token = self.get_token();
+            let right = match token.kind {
+                TokenKind::Ident(x) => x,
+                // the constructor can be called again like any other method
+                TokenKind::Keyword(KeywordKind::New) => "new".to_string(),
+                _ => panic!("Dot expression rvalue must be ident"),
+            };
+            let args = match self.peek_token().map(|x| x.kind) {
+                Some(TokenKind::Symbol(SymbolKind::LeftBracket)) => Some(self.parse_args()),
+                _ => None,
+            };
+            node = Node::DotExpr {
+                left: Box::new(node),
+                right,
+                args,
+                pos: token.start,
+            };
+        }
+        node
     }
 
+    /// Parses a full expression, including boolean and comparison operators
     fn parse_cond(&mut self) -> Node {
         info!("Parsing conditional");
 
-        let left = self.parse_expr();
-        let optok = self.peek_token();
-        if let Some(x) = optok {
-            let operator = match x.kind {
-                TokenKind::Symbol(SymbolKind::Greater) => Op::Greater,
-                TokenKind::Symbol(SymbolKind::GreaterEquals) => Op::GreaterEqual,
-                TokenKind::Symbol(SymbolKind::Less) => Op::Less,
-                TokenKind::Symbol(SymbolKind::LessEquals) => Op::LessEqual,
-                TokenKind::Symbol(SymbolKind::DoubleEquals) => Op::EqualTo,
-                _ => return left,
-            };
-            self.get_token(); // consume token
-            let right = self.parse_cond();
-            Node::BinaryExpr {
-                left: Box::new(left),
-                operator,
-                right: Box::new(right),
-            }
-        } else {
-            left
-        }
+        self.parse_binary(Precedence::Or)
     }
 
-    fn parse_expr(&mut self) -> Node {
-        info!("Parsing expresion");
-
-        let left = self.parse_term();
-        let optok = self.peek_token();
-        if let Some(x) = optok {
-            let operator = match x.kind {
-                TokenKind::Symbol(SymbolKind::Plus) => Op::Plus,
-                TokenKind::Symbol(SymbolKind::Minus) => Op::Minus,
-                _ => return left,
+    /// Precedence climbing: parses an operand, then keeps folding in binary
+    /// operators that bind at least as tightly as `min`. Left associative
+    /// operators parse their right operand one level higher, so that
+    /// `10 - 5 - 2` groups as `(10 - 5) - 2`.
+    fn parse_binary(&mut self, min: Precedence) -> Node {
+        info!("Parsing binary expression");
+
+        let mut left = self.parse_prefix();
+        while let Some(x) = self.peek_token() {
+            let (operator, precedence) = match Self::binary_operator(&x.kind) {
+                Some(op) if op.1 >= min => op,
+                _ => break,
             };
             self.get_token(); // consume token
-            let right = self.parse_expr();
-            Node::BinaryExpr {
+
+            let right = if operator == Op::Power {
+                self.parse_binary(precedence)
+            } else {
+                self.parse_binary(precedence.next())
+            };
+            left = Node::BinaryExpr {
                 left: Box::new(left),
                 operator,
                 right: Box::new(right),
-            }
-        } else {
-            left
+                pos: x.start,
+            };
         }
+        left
     }
 
-    fn parse_term(&mut self) -> Node {
-        info!("Parsing term");
-        // for now, we will skip this
-        let left = self.parse_factor();
-        let optok = self.peek_token();
-        if let Some(x) = optok {
-            let operator = match x.kind {
-                TokenKind::Symbol(SymbolKind::Multiply) => Op::Multiply,
-                TokenKind::Symbol(SymbolKind::Divide) => Op::Divide,
-                TokenKind::Symbol(SymbolKind::Mod) => Op::Mod,
-                _ => return left,
-            };
-            self.get_token(); // consume token
-            let right = self.parse_expr();
-            Node::BinaryExpr {
-                left: Box::new(left),
-                operator,
-                right: Box::new(right),
+    fn binary_operator(kind: &TokenKind) -> Option<(Op, Precedence)> {
+        let precedence = match kind {
+            TokenKind::Symbol(SymbolKind::Or) => Precedence::Or,
+            TokenKind::Symbol(SymbolKind::And) => Precedence::And,
+            TokenKind::Symbol(
+                SymbolKind::Greater
+                | SymbolKind::GreaterEquals
+                | SymbolKind::Less
+                | SymbolKind::LessEquals
+                | SymbolKind::DoubleEquals
+                | SymbolKind::NotEquals,
+            ) => Precedence::Comparison,
+            TokenKind::Symbol(SymbolKind::Plus | SymbolKind::Minus) => Precedence::Sum,
+            TokenKind::Symbol(
+                SymbolKind::Multiply | SymbolKind::Divide | SymbolKind::IntDivide | SymbolKind::Mod,
+            ) => Precedence::Product,
+            TokenKind::Symbol(SymbolKind::Power) => Precedence::Power,
+            _ => return None,
+        };
+        Some((Op::from(kind.clone()), precedence))
+    }
+
+    /// Prefix operators apply to everything that binds tighter than them, so
+    /// `not a == b` is `not (a == b)` and `-2 ^ 2` is `-(2 ^ 2)`
+    fn parse_prefix(&mut self) -> Node {
+        info!("Parsing prefix");
+
+        let token = self.peek_token().unwrap();
+        let operand = match token.kind {
+            TokenKind::Symbol(SymbolKind::Not) => Precedence::Comparison,
+            TokenKind::Symbol(SymbolKind::Minus) | TokenKind::Symbol(SymbolKind::Plus) => {
+                Precedence::Power
             }
-        } else {
-            left
+            _ => return self.parse_factor(),
+        };
+        self.get_token(); // consume token
+        Node::UnaryExpr {
+            operator: Op::from(token.kind),
+            expr: Box::new(self.parse_binary(operand)),
+            pos: token.start,
         }
     }
 
     fn parse_factor(&mut self) -> Node {
         info!("Parsing factor");
+        let primary = self.parse_primary();
+        self.parse_dot_expr(primary)
+    }
+
+    fn parse_primary(&mut self) -> Node {
+        info!("Parsing primary");
         let token = self.peek_token().unwrap();
         match token.kind {
             TokenKind::Number(x) => {
                 self.get_token();
                 Node::Primary(Value::Number(x))
             }
+            TokenKind::Real(x) => {
+                self.get_token();
+                Node::Primary(Value::Real(x))
+            }
             TokenKind::String(x) => {
                 self.get_token();
                 Node::Primary(Value::String(x))
             }
+            TokenKind::Boolean(x) => {
+                self.get_token();
+                Node::Primary(Value::Boolean(x))
+            }
             TokenKind::Ident(x) => {
                 let mut peekpeek = self.tokens.clone();
                 peekpeek.pop();
@@ -322,13 +746,48 @@ impl Parser {
                     } else if x.kind == TokenKind::Symbol(SymbolKind::LeftSqBracket) {
                         info!("Array ref as factor");
                         return self.parse_array_ref();
-                    } else if x.kind == TokenKind::Symbol(SymbolKind::Dot) {
-                        info!("Dot as factor");
-                        return self.parse_dot_expr();
                     }
                 }
                 self.get_token();
-                Node::VariableRef(x)
+                Node::VariableRef {
+                    ident: x,
+                    pos: token.start,
+                }
+            }
+            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_array_literal(),
+            TokenKind::Keyword(KeywordKind::New) => {
+                self.get_token(); // consume "new"
+                let class = match self.get_token().kind {
+                    TokenKind::Ident(x) => x,
+                    _ => panic!("new must be followed by a class name"),
+                };
+                Node::New {
+                    class,
+                    args: self.parse_args(),
+                    pos: token.start,
+                }
+            }
+            TokenKind::Keyword(KeywordKind::Super) => {
+                self.get_token(); // consume "super"
+                match self.get_token().kind {
+                    TokenKind::Symbol(SymbolKind::Dot) => {}
+                    _ => panic!("super must be followed by a method call"),
+                }
+                let token = self.get_token();
+                let method = match token.kind {
+                    TokenKind::Ident(x) => x,
+                    TokenKind::Keyword(KeywordKind::New) => "new".to_string(),
+                    _ => panic!("super must be followed by a method call"),
+                };
+                let args = match self.peek_token().map(|x| x.kind) {
+                    Some(TokenKind::Symbol(SymbolKind::LeftBracket)) => self.parse_args(),
+                    _ => Vec::new(),
+                };
+                Node::SuperCall {
+                    method,
+                    args,
+                    pos: token.start,
+                }
             }
             TokenKind::Symbol(SymbolKind::LeftBracket) => {
                 self.get_token();
@@ -341,22 +800,41 @@ impl Parser {
         }
     }
 
+    fn parse_array_literal(&mut self) -> Node {
+        info!("Parsing array literal");
+
+        self.get_token(); // consume '['
+        let mut elements = Vec::new();
+        if self.peek_token().map(|x| x.kind) != Some(TokenKind::Symbol(SymbolKind::RightSqBracket))
+        {
+            elements.push(self.parse_cond());
+            while let Some(TokenKind::Symbol(SymbolKind::Comma)) = self.peek_token().map(|x| x.kind)
+            {
+                self.get_token(); // consume ','
+                elements.push(self.parse_cond());
+            }
+        }
+        self.get_token(); // consume ']'
+
+        Node::ArrayLiteral(elements)
+    }
+
     fn parse_array_ref(&mut self) -> Node {
-        let ident = match self.get_token().kind {
+        let token = self.get_token();
+        let ident = match token.kind {
             TokenKind::Ident(x) => x,
             _ => panic!("array ref must have ident"),
         };
 
-        let index = match self.get_token().kind {
-            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_cond(),
+        let indices = match self.get_token().kind {
+            TokenKind::Symbol(SymbolKind::LeftSqBracket) => self.parse_index_list(),
             _ => panic!("array index must be specified with square brackets"),
         };
 
-        self.get_token(); // consume final ']'
-
         Node::ArrayRef {
             ident,
-            index: Box::new(index),
+            indices,
+            pos: token.start,
         }
     }
 
@@ -388,9 +866,79 @@ impl Parser {
     }
 }
 
+/// Whether a token closes the block it appears in, such as `endif` or `next`
+fn ends_block(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Keyword(
+            KeywordKind::EndIf
+                | KeywordKind::Else
+                | KeywordKind::ElseIf
+                | KeywordKind::EndWhile
+                | KeywordKind::EndFunction
+                | KeywordKind::EndProcedure
+                | KeywordKind::Next
+                | KeywordKind::Until
+                | KeywordKind::Case
+                | KeywordKind::Default
+                | KeywordKind::EndSwitch
+        )
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Position;
+
+    /// Parses `x = <expr>` and returns the expression node
+    fn parse_assigned_expr(expr: Vec<TokenKind>) -> Node {
+        let mut input = vec![
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+        ];
+        input.extend(expr);
+
+        match Parser::parse_from_list(input).unwrap() {
+            Node::Block(mut nodes) => match nodes.remove(0) {
+                Node::Assign { value, .. } => *value,
+                x => panic!("expected assignment, got {:?}", x),
+            },
+            x => panic!("expected block, got {:?}", x),
+        }
+    }
+
+    fn binary(left: Node, operator: Op, right: Node) -> Node {
+        Node::BinaryExpr {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+            pos: Position::new(0, 0),
+        }
+    }
+
+    fn unary(operator: Op, expr: Node) -> Node {
+        Node::UnaryExpr {
+            operator,
+            expr: Box::new(expr),
+            pos: Position::new(0, 0),
+        }
+    }
+
+    fn num(x: i64) -> Node {
+        Node::Primary(Value::Number(x))
+    }
+
+    fn var(ident: &str) -> Node {
+        Node::VariableRef {
+            ident: ident.to_string(),
+            pos: Position::new(0, 0),
+        }
+    }
+
+    fn sym(kind: SymbolKind) -> TokenKind {
+        TokenKind::Symbol(kind)
+    }
 
     #[test]
     fn primary_assign() {
@@ -404,7 +952,8 @@ mod tests {
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::Assign {
                 ident: "num".to_string(),
-                value: Box::new(Node::Primary(Value::Number(10)))
+                value: Box::new(Node::Primary(Value::Number(10))),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -426,8 +975,10 @@ mod tests {
                 value: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Plus,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(5)))
-                })
+                }),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -451,12 +1002,15 @@ mod tests {
                 value: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Plus,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::BinaryExpr {
                         left: Box::new(Node::Primary(Value::Number(5))),
                         operator: Op::Multiply,
+                        pos: Position::new(0, 0),
                         right: Box::new(Node::Primary(Value::Number(2)))
                     })
-                })
+                }),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -483,11 +1037,14 @@ mod tests {
                     left: Box::new(Node::BinaryExpr {
                         left: Box::new(Node::Primary(Value::Number(10))),
                         operator: Op::Plus,
+                        pos: Position::new(0, 0),
                         right: Box::new(Node::Primary(Value::Number(5)))
                     }),
                     operator: Op::Multiply,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(2)))
-                })
+                }),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -504,7 +1061,8 @@ mod tests {
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::Assign {
                 ident: "str".to_string(),
-                value: Box::new(Node::Primary(Value::String("hello world".to_string())))
+                value: Box::new(Node::Primary(Value::String("hello world".to_string()))),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -521,6 +1079,7 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
                 ident: "print".to_string(),
                 args: vec![Node::Primary(Value::String("hello world".to_string()))]
             }])
@@ -539,8 +1098,9 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
                 ident: "print".to_string(),
-                args: vec![Node::VariableRef("str".to_string())]
+                args: vec![var("str")]
             }])
         );
     }
@@ -560,9 +1120,11 @@ mod tests {
             Node::Block(vec![Node::Assign {
                 ident: "in".to_string(),
                 value: Box::new(Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "input".to_string(),
                     args: vec![]
-                })
+                }),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -588,13 +1150,17 @@ mod tests {
                 expr: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Greater,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(5)))
                 }),
                 then: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("hello world".to_string()))]
                 }])),
-                els: Box::new(Node::Block(vec![]))
+                elseifs: vec![],
+                els: Box::new(Node::Block(vec![])),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -625,16 +1191,21 @@ mod tests {
                 expr: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Greater,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(5)))
                 }),
                 then: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("hello world".to_string()))]
                 }])),
+                elseifs: vec![],
                 els: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("goodbye world".to_string()))]
-                }]))
+                }])),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -659,12 +1230,15 @@ mod tests {
                 expr: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Greater,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(5)))
                 }),
                 body: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("hello world".to_string()))]
-                }]))
+                }])),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -693,16 +1267,21 @@ mod tests {
                     left: Box::new(Node::BinaryExpr {
                         left: Box::new(Node::Primary(Value::Number(10))),
                         operator: Op::Plus,
+                        pos: Position::new(0, 0),
                         right: Box::new(Node::Primary(Value::Number(5)))
                     }),
                     operator: Op::Greater,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::Primary(Value::Number(5)))
                 }),
                 then: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("hello world".to_string()))]
                 }])),
-                els: Box::new(Node::Block(vec![]))
+                elseifs: vec![],
+                els: Box::new(Node::Block(vec![])),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -730,17 +1309,22 @@ mod tests {
                 expr: Box::new(Node::BinaryExpr {
                     left: Box::new(Node::Primary(Value::Number(10))),
                     operator: Op::Greater,
+                    pos: Position::new(0, 0),
                     right: Box::new(Node::BinaryExpr {
                         left: Box::new(Node::Primary(Value::Number(5))),
                         operator: Op::Plus,
+                        pos: Position::new(0, 0),
                         right: Box::new(Node::Primary(Value::Number(5)))
                     })
                 }),
                 then: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
                     ident: "print".to_string(),
                     args: vec![Node::Primary(Value::String("hello world".to_string()))]
                 }])),
-                els: Box::new(Node::Block(vec![]))
+                elseifs: vec![],
+                els: Box::new(Node::Block(vec![])),
+                pos: Position::new(0, 0),
             }])
         );
     }
@@ -758,8 +1342,9 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::ArrayAssign {
+                pos: Position::new(0, 0),
                 ident: "arr".to_string(),
-                size: Box::new(Node::Primary(Value::Number(10)))
+                sizes: vec![Node::Primary(Value::Number(10))]
             }])
         );
     }
@@ -778,8 +1363,9 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::ArrayAssingIndex {
+                pos: Position::new(0, 0),
                 ident: "arr".to_string(),
-                index: Box::new(Node::Primary(Value::Number(10))),
+                indices: vec![Node::Primary(Value::Number(10))],
                 value: Box::new(Node::Primary(Value::Number(5)))
             }])
         );
@@ -800,10 +1386,12 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
                 ident: "print".to_string(),
                 args: vec![Node::ArrayRef {
+                    pos: Position::new(0, 0),
                     ident: "arr".to_string(),
-                    index: Box::new(Node::Primary(Value::Number(10)))
+                    indices: vec![Node::Primary(Value::Number(10))]
                 }]
             }])
         );
@@ -821,8 +1409,9 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
                 ident: "print".to_string(),
-                args: vec![Node::VariableRef("foo".to_string())]
+                args: vec![var("foo")]
             }])
         );
     }
@@ -841,12 +1430,1229 @@ mod tests {
         assert_eq!(
             Parser::parse_from_list(input).unwrap(),
             Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
                 ident: "print".to_string(),
                 args: vec![Node::DotExpr {
-                    left: "foo".to_string(),
-                    right: "length".to_string()
+                    left: Box::new(var("foo")),
+                    right: "length".to_string(),
+                    args: None,
+                    pos: Position::new(0, 0),
                 }]
             }])
         );
     }
+
+    #[test]
+    fn function_call_multiple_args() {
+        let input = vec![
+            TokenKind::Ident("add".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Number(1),
+            TokenKind::Symbol(SymbolKind::Comma),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::FuncCall {
+                pos: Position::new(0, 0),
+                ident: "add".to_string(),
+                args: vec![Node::Primary(Value::Number(1)), var("x")]
+            }])
+        );
+    }
+
+    #[test]
+    fn function_decl() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Function),
+            TokenKind::Ident("add".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Ident("a".to_string()),
+            TokenKind::Symbol(SymbolKind::Comma),
+            TokenKind::Ident("b".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::Return),
+            TokenKind::Ident("a".to_string()),
+            TokenKind::Symbol(SymbolKind::Plus),
+            TokenKind::Ident("b".to_string()),
+            TokenKind::Keyword(KeywordKind::EndFunction),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::FunctionDecl {
+                ident: "add".to_string(),
+                params: vec![
+                    Param {
+                        ident: "a".to_string(),
+                        passing: Passing::Default,
+                    },
+                    Param {
+                        ident: "b".to_string(),
+                        passing: Passing::Default,
+                    },
+                ],
+                body: Box::new(Node::Block(vec![Node::Return {
+                    value: Some(Box::new(Node::BinaryExpr {
+                        left: Box::new(var("a")),
+                        operator: Op::Plus,
+                        pos: Position::new(0, 0),
+                        right: Box::new(var("b"))
+                    })),
+                    pos: Position::new(0, 0),
+                }]))
+            }])
+        );
+    }
+
+    #[test]
+    fn procedure_decl() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Procedure),
+            TokenKind::Ident("greet".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Ident("print".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::String("hello".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::EndProcedure),
+            TokenKind::Ident("greet".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![
+                Node::ProcedureDecl {
+                    ident: "greet".to_string(),
+                    params: vec![],
+                    body: Box::new(Node::Block(vec![Node::FuncCall {
+                        pos: Position::new(0, 0),
+                        ident: "print".to_string(),
+                        args: vec![Node::Primary(Value::String("hello".to_string()))]
+                    }]))
+                },
+                Node::FuncCall {
+                    pos: Position::new(0, 0),
+                    ident: "greet".to_string(),
+                    args: vec![]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn procedure_bare_return() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Procedure),
+            TokenKind::Ident("stop".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::Return),
+            TokenKind::Keyword(KeywordKind::EndProcedure),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ProcedureDecl {
+                ident: "stop".to_string(),
+                params: vec![],
+                body: Box::new(Node::Block(vec![Node::Return {
+                    value: None,
+                    pos: Position::new(0, 0),
+                }]))
+            }])
+        );
+    }
+
+    #[test]
+    fn for_loop() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::For),
+            TokenKind::Ident("i".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(0),
+            TokenKind::Keyword(KeywordKind::To),
+            TokenKind::Number(9),
+            TokenKind::Ident("print".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::Ident("i".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::Next),
+            TokenKind::Ident("i".to_string()),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ForStmt {
+                ident: "i".to_string(),
+                start: Box::new(Node::Primary(Value::Number(0))),
+                end: Box::new(Node::Primary(Value::Number(9))),
+                step: None,
+                body: Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
+                    ident: "print".to_string(),
+                    args: vec![var("i")]
+                }])),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn for_loop_negative_step() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::For),
+            TokenKind::Ident("i".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(10),
+            TokenKind::Keyword(KeywordKind::To),
+            TokenKind::Number(0),
+            TokenKind::Keyword(KeywordKind::Step),
+            TokenKind::Symbol(SymbolKind::Minus),
+            TokenKind::Number(2),
+            TokenKind::Keyword(KeywordKind::Next),
+            TokenKind::Ident("i".to_string()),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ForStmt {
+                ident: "i".to_string(),
+                start: Box::new(Node::Primary(Value::Number(10))),
+                end: Box::new(Node::Primary(Value::Number(0))),
+                step: Some(Box::new(Node::UnaryExpr {
+                    operator: Op::Minus,
+                    expr: Box::new(Node::Primary(Value::Number(2))),
+                    pos: Position::new(0, 0),
+                })),
+                body: Box::new(Node::Block(vec![])),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn for_loop_mismatched_next() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::For),
+            TokenKind::Ident("i".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(0),
+            TokenKind::Keyword(KeywordKind::To),
+            TokenKind::Number(9),
+            TokenKind::Keyword(KeywordKind::Next),
+            TokenKind::Ident("j".to_string()),
+        ];
+
+        assert!(matches!(
+            Parser::parse_from_list(input),
+            Err(ParserError::MismatchedNextVariable(_, _, _))
+        ));
+    }
+
+    #[test]
+    fn do_until_loop() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Do),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Plus),
+            TokenKind::Number(1),
+            TokenKind::Keyword(KeywordKind::Until),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::DoubleEquals),
+            TokenKind::Number(10),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::DoUntil {
+                body: Box::new(Node::Block(vec![Node::Assign {
+                    ident: "x".to_string(),
+                    value: Box::new(Node::BinaryExpr {
+                        left: Box::new(var("x")),
+                        operator: Op::Plus,
+                        pos: Position::new(0, 0),
+                        right: Box::new(Node::Primary(Value::Number(1)))
+                    }),
+                    pos: Position::new(0, 0),
+                }])),
+                expr: Box::new(Node::BinaryExpr {
+                    left: Box::new(var("x")),
+                    operator: Op::EqualTo,
+                    pos: Position::new(0, 0),
+                    right: Box::new(Node::Primary(Value::Number(10)))
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn switch_statement() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Switch),
+            TokenKind::Ident("choice".to_string()),
+            TokenKind::Symbol(SymbolKind::Colon),
+            TokenKind::Keyword(KeywordKind::Case),
+            TokenKind::Number(1),
+            TokenKind::Symbol(SymbolKind::Colon),
+            TokenKind::Ident("print".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::String("one".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::Case),
+            TokenKind::Number(2),
+            TokenKind::Symbol(SymbolKind::Colon),
+            TokenKind::Keyword(KeywordKind::Default),
+            TokenKind::Symbol(SymbolKind::Colon),
+            TokenKind::Ident("print".to_string()),
+            TokenKind::Symbol(SymbolKind::LeftBracket),
+            TokenKind::String("other".to_string()),
+            TokenKind::Symbol(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::EndSwitch),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Switch {
+                expr: Box::new(var("choice")),
+                cases: vec![
+                    (
+                        Node::Primary(Value::Number(1)),
+                        Node::Block(vec![Node::FuncCall {
+                            pos: Position::new(0, 0),
+                            ident: "print".to_string(),
+                            args: vec![Node::Primary(Value::String("one".to_string()))]
+                        }])
+                    ),
+                    (Node::Primary(Value::Number(2)), Node::Block(vec![]))
+                ],
+                default: Some(Box::new(Node::Block(vec![Node::FuncCall {
+                    pos: Position::new(0, 0),
+                    ident: "print".to_string(),
+                    args: vec![Node::Primary(Value::String("other".to_string()))]
+                }])))
+            }])
+        );
+    }
+
+    #[test]
+    fn switch_invalid_token() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Switch),
+            TokenKind::Ident("choice".to_string()),
+            TokenKind::Symbol(SymbolKind::Colon),
+            TokenKind::Number(1),
+            TokenKind::Keyword(KeywordKind::EndSwitch),
+        ];
+
+        assert!(matches!(
+            Parser::parse_from_list(input),
+            Err(ParserError::InvalidTokenInSwitch(_, _))
+        ));
+    }
+
+    #[test]
+    fn elseif_chain() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::If),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Greater),
+            TokenKind::Number(5),
+            TokenKind::Keyword(KeywordKind::Then),
+            TokenKind::Ident("y".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(1),
+            TokenKind::Keyword(KeywordKind::ElseIf),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Greater),
+            TokenKind::Number(2),
+            TokenKind::Keyword(KeywordKind::Then),
+            TokenKind::Ident("y".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(2),
+            TokenKind::Keyword(KeywordKind::ElseIf),
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Greater),
+            TokenKind::Number(1),
+            TokenKind::Keyword(KeywordKind::Then),
+            TokenKind::Ident("y".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(3),
+            TokenKind::Keyword(KeywordKind::Else),
+            TokenKind::Ident("y".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(4),
+            TokenKind::Keyword(KeywordKind::EndIf),
+        ];
+
+        let assign = |value| Node::Assign {
+            ident: "y".to_string(),
+            value: Box::new(Node::Primary(Value::Number(value))),
+            pos: Position::new(0, 0),
+        };
+        let greater = |value| Node::BinaryExpr {
+            left: Box::new(var("x")),
+            operator: Op::Greater,
+            pos: Position::new(0, 0),
+            right: Box::new(Node::Primary(Value::Number(value))),
+        };
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::IfExpr {
+                expr: Box::new(greater(5)),
+                then: Box::new(Node::Block(vec![assign(1)])),
+                elseifs: vec![
+                    (
+                        greater(2),
+                        Node::Block(vec![assign(2)]),
+                        Position::new(0, 0)
+                    ),
+                    (
+                        greater(1),
+                        Node::Block(vec![assign(3)]),
+                        Position::new(0, 0)
+                    ),
+                ],
+                els: Box::new(Node::Block(vec![assign(4)])),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn boolean_operator_precedence() {
+        let input = vec![
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Ident("a".to_string()),
+            TokenKind::Symbol(SymbolKind::Or),
+            TokenKind::Symbol(SymbolKind::Not),
+            TokenKind::Ident("b".to_string()),
+            TokenKind::Symbol(SymbolKind::And),
+            TokenKind::Number(1),
+            TokenKind::Symbol(SymbolKind::Less),
+            TokenKind::Number(2),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "x".to_string(),
+                value: Box::new(Node::BinaryExpr {
+                    left: Box::new(var("a")),
+                    operator: Op::Or,
+                    pos: Position::new(0, 0),
+                    right: Box::new(Node::BinaryExpr {
+                        left: Box::new(Node::UnaryExpr {
+                            operator: Op::Not,
+                            pos: Position::new(0, 0),
+                            expr: Box::new(var("b"))
+                        }),
+                        operator: Op::And,
+                        pos: Position::new(0, 0),
+                        right: Box::new(Node::BinaryExpr {
+                            left: Box::new(Node::Primary(Value::Number(1))),
+                            operator: Op::Less,
+                            pos: Position::new(0, 0),
+                            right: Box::new(Node::Primary(Value::Number(2)))
+                        })
+                    })
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn boolean_assign() {
+        let input = vec![
+            TokenKind::Ident("found".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Boolean(false),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "found".to_string(),
+                value: Box::new(Node::Primary(Value::Boolean(false))),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn not_equal_comparison() {
+        let input = vec![
+            TokenKind::Ident("same".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::String("a".to_string()),
+            TokenKind::Symbol(SymbolKind::NotEquals),
+            TokenKind::String("b".to_string()),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "same".to_string(),
+                value: Box::new(Node::BinaryExpr {
+                    left: Box::new(Node::Primary(Value::String("a".to_string()))),
+                    operator: Op::NotEqualTo,
+                    pos: Position::new(0, 0),
+                    right: Box::new(Node::Primary(Value::String("b".to_string())))
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn real_assign() {
+        let input = vec![
+            TokenKind::Ident("half".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Real(2.5),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "half".to_string(),
+                value: Box::new(Node::Primary(Value::Real(2.5))),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn unary_minus() {
+        let input = vec![
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Symbol(SymbolKind::Minus),
+            TokenKind::Number(4),
+            TokenKind::Symbol(SymbolKind::Multiply),
+            TokenKind::Symbol(SymbolKind::Minus),
+            TokenKind::Ident("y".to_string()),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "x".to_string(),
+                value: Box::new(Node::BinaryExpr {
+                    left: Box::new(Node::UnaryExpr {
+                        operator: Op::Minus,
+                        expr: Box::new(Node::Primary(Value::Number(4))),
+                        pos: Position::new(0, 0),
+                    }),
+                    operator: Op::Multiply,
+                    pos: Position::new(0, 0),
+                    right: Box::new(Node::UnaryExpr {
+                        operator: Op::Minus,
+                        expr: Box::new(var("y")),
+                        pos: Position::new(0, 0),
+                    })
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn power_right_associative() {
+        let input = vec![
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Symbol(SymbolKind::Minus),
+            TokenKind::Number(2),
+            TokenKind::Symbol(SymbolKind::Power),
+            TokenKind::Number(3),
+            TokenKind::Symbol(SymbolKind::Power),
+            TokenKind::Number(2),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "x".to_string(),
+                value: Box::new(Node::UnaryExpr {
+                    operator: Op::Minus,
+                    pos: Position::new(0, 0),
+                    expr: Box::new(Node::BinaryExpr {
+                        left: Box::new(Node::Primary(Value::Number(2))),
+                        operator: Op::Power,
+                        pos: Position::new(0, 0),
+                        right: Box::new(Node::BinaryExpr {
+                            left: Box::new(Node::Primary(Value::Number(3))),
+                            operator: Op::Power,
+                            pos: Position::new(0, 0),
+                            right: Box::new(Node::Primary(Value::Number(2)))
+                        })
+                    })
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn power_binds_tighter_than_multiply() {
+        let input = vec![
+            TokenKind::Ident("x".to_string()),
+            TokenKind::Symbol(SymbolKind::Equals),
+            TokenKind::Number(2),
+            TokenKind::Symbol(SymbolKind::IntDivide),
+            TokenKind::Number(3),
+            TokenKind::Symbol(SymbolKind::Power),
+            TokenKind::Number(2),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::Assign {
+                ident: "x".to_string(),
+                value: Box::new(Node::BinaryExpr {
+                    left: Box::new(Node::Primary(Value::Number(2))),
+                    operator: Op::IntDivide,
+                    pos: Position::new(0, 0),
+                    right: Box::new(Node::BinaryExpr {
+                        left: Box::new(Node::Primary(Value::Number(3))),
+                        operator: Op::Power,
+                        pos: Position::new(0, 0),
+                        right: Box::new(Node::Primary(Value::Number(2)))
+                    })
+                }),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn or_left_associative() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Ident("a".to_string()),
+            sym(SymbolKind::Or),
+            TokenKind::Ident("b".to_string()),
+            sym(SymbolKind::Or),
+            TokenKind::Ident("c".to_string()),
+        ]);
+        assert_eq!(
+            expr,
+            binary(binary(var("a"), Op::Or, var("b")), Op::Or, var("c"))
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Ident("a".to_string()),
+            sym(SymbolKind::And),
+            TokenKind::Ident("b".to_string()),
+            sym(SymbolKind::Or),
+            TokenKind::Ident("c".to_string()),
+            sym(SymbolKind::And),
+            TokenKind::Ident("d".to_string()),
+        ]);
+        assert_eq!(
+            expr,
+            binary(
+                binary(var("a"), Op::And, var("b")),
+                Op::Or,
+                binary(var("c"), Op::And, var("d"))
+            )
+        );
+    }
+
+    #[test]
+    fn not_binds_looser_than_comparison() {
+        let expr = parse_assigned_expr(vec![
+            sym(SymbolKind::Not),
+            TokenKind::Ident("a".to_string()),
+            sym(SymbolKind::DoubleEquals),
+            TokenKind::Ident("b".to_string()),
+            sym(SymbolKind::And),
+            TokenKind::Ident("c".to_string()),
+        ]);
+        assert_eq!(
+            expr,
+            binary(
+                unary(Op::Not, binary(var("a"), Op::EqualTo, var("b"))),
+                Op::And,
+                var("c")
+            )
+        );
+    }
+
+    #[test]
+    fn comparison_binds_looser_than_sum() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Ident("a".to_string()),
+            sym(SymbolKind::Plus),
+            TokenKind::Number(1),
+            sym(SymbolKind::LessEquals),
+            TokenKind::Ident("b".to_string()),
+            sym(SymbolKind::Minus),
+            TokenKind::Number(1),
+        ]);
+        assert_eq!(
+            expr,
+            binary(
+                binary(var("a"), Op::Plus, num(1)),
+                Op::LessEqual,
+                binary(var("b"), Op::Minus, num(1))
+            )
+        );
+    }
+
+    #[test]
+    fn subtraction_left_associative() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Number(10),
+            sym(SymbolKind::Minus),
+            TokenKind::Number(5),
+            sym(SymbolKind::Minus),
+            TokenKind::Number(2),
+        ]);
+        assert_eq!(
+            expr,
+            binary(binary(num(10), Op::Minus, num(5)), Op::Minus, num(2))
+        );
+    }
+
+    #[test]
+    fn division_left_associative() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Number(8),
+            sym(SymbolKind::Divide),
+            TokenKind::Number(4),
+            sym(SymbolKind::Divide),
+            TokenKind::Number(2),
+        ]);
+        assert_eq!(
+            expr,
+            binary(binary(num(8), Op::Divide, num(4)), Op::Divide, num(2))
+        );
+    }
+
+    #[test]
+    fn product_binds_tighter_than_sum() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Number(5),
+            sym(SymbolKind::Multiply),
+            TokenKind::Number(2),
+            sym(SymbolKind::Plus),
+            TokenKind::Number(10),
+            sym(SymbolKind::Mod),
+            TokenKind::Number(3),
+        ]);
+        assert_eq!(
+            expr,
+            binary(
+                binary(num(5), Op::Multiply, num(2)),
+                Op::Plus,
+                binary(num(10), Op::Mod, num(3))
+            )
+        );
+    }
+
+    #[test]
+    fn unary_minus_in_sum() {
+        let expr = parse_assigned_expr(vec![
+            sym(SymbolKind::Minus),
+            TokenKind::Number(1),
+            sym(SymbolKind::Minus),
+            sym(SymbolKind::Minus),
+            TokenKind::Number(2),
+        ]);
+        assert_eq!(
+            expr,
+            binary(
+                unary(Op::Minus, num(1)),
+                Op::Minus,
+                unary(Op::Minus, num(2))
+            )
+        );
+    }
+
+    #[test]
+    fn brackets_override_precedence() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Number(10),
+            sym(SymbolKind::Minus),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::Number(5),
+            sym(SymbolKind::Minus),
+            TokenKind::Number(2),
+            sym(SymbolKind::RightBracket),
+        ]);
+        assert_eq!(
+            expr,
+            binary(num(10), Op::Minus, binary(num(5), Op::Minus, num(2)))
+        );
+    }
+
+    #[test]
+    fn method_call_chain() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::Ident("name".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("substring".to_string()),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::Number(1),
+            sym(SymbolKind::Comma),
+            TokenKind::Number(3),
+            sym(SymbolKind::RightBracket),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("upper".to_string()),
+        ]);
+        assert_eq!(
+            expr,
+            Node::DotExpr {
+                left: Box::new(Node::DotExpr {
+                    left: Box::new(var("name")),
+                    right: "substring".to_string(),
+                    args: Some(vec![num(1), num(3)]),
+                    pos: Position::new(0, 0),
+                }),
+                right: "upper".to_string(),
+                args: None,
+                pos: Position::new(0, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn method_call_on_literal() {
+        let expr = parse_assigned_expr(vec![
+            TokenKind::String("abc".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("left".to_string()),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::Number(2),
+            sym(SymbolKind::RightBracket),
+        ]);
+        assert_eq!(
+            expr,
+            Node::DotExpr {
+                left: Box::new(Node::Primary(Value::String("abc".to_string()))),
+                right: "left".to_string(),
+                args: Some(vec![num(2)]),
+                pos: Position::new(0, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn method_call_statement() {
+        let input = vec![
+            TokenKind::Ident("file".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("writeLine".to_string()),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::String("hello".to_string()),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Ident("file".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("close".to_string()),
+            sym(SymbolKind::LeftBracket),
+            sym(SymbolKind::RightBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![
+                Node::DotExpr {
+                    left: Box::new(var("file")),
+                    right: "writeLine".to_string(),
+                    args: Some(vec![Node::Primary(Value::String("hello".to_string()))]),
+                    pos: Position::new(0, 0),
+                },
+                Node::DotExpr {
+                    left: Box::new(var("file")),
+                    right: "close".to_string(),
+                    args: Some(vec![]),
+                    pos: Position::new(0, 0),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn array_literal_declaration() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Array),
+            TokenKind::Ident("names".to_string()),
+            sym(SymbolKind::Equals),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::String("a".to_string()),
+            sym(SymbolKind::Comma),
+            TokenKind::String("b".to_string()),
+            sym(SymbolKind::RightSqBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ArrayLiteralAssign {
+                ident: "names".to_string(),
+                elements: vec![
+                    Node::Primary(Value::String("a".to_string())),
+                    Node::Primary(Value::String("b".to_string())),
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn array_declaration_needs_literal() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Array),
+            TokenKind::Ident("names".to_string()),
+            sym(SymbolKind::Equals),
+            TokenKind::Number(5),
+        ];
+
+        assert!(matches!(
+            Parser::parse_from_list(input),
+            Err(ParserError::ExpectedArrayLiteral(..))
+        ));
+    }
+
+    #[test]
+    fn array_literal_expression() {
+        let expr = parse_assigned_expr(vec![
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(1),
+            sym(SymbolKind::Plus),
+            TokenKind::Number(2),
+            sym(SymbolKind::Comma),
+            sym(SymbolKind::LeftSqBracket),
+            sym(SymbolKind::RightSqBracket),
+            sym(SymbolKind::RightSqBracket),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("length".to_string()),
+        ]);
+        assert_eq!(
+            expr,
+            Node::DotExpr {
+                left: Box::new(Node::ArrayLiteral(vec![
+                    binary(num(1), Op::Plus, num(2)),
+                    Node::ArrayLiteral(vec![]),
+                ])),
+                right: "length".to_string(),
+                args: None,
+                pos: Position::new(0, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn two_dimensional_array() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Array),
+            TokenKind::Ident("board".to_string()),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(3),
+            sym(SymbolKind::Comma),
+            TokenKind::Number(3),
+            sym(SymbolKind::RightSqBracket),
+            TokenKind::Ident("board".to_string()),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(1),
+            sym(SymbolKind::Comma),
+            TokenKind::Ident("j".to_string()),
+            sym(SymbolKind::RightSqBracket),
+            sym(SymbolKind::Equals),
+            TokenKind::Ident("board".to_string()),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(0),
+            sym(SymbolKind::Comma),
+            TokenKind::Number(2),
+            sym(SymbolKind::RightSqBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![
+                Node::ArrayAssign {
+                    pos: Position::new(0, 0),
+                    ident: "board".to_string(),
+                    sizes: vec![num(3), num(3)],
+                },
+                Node::ArrayAssingIndex {
+                    ident: "board".to_string(),
+                    indices: vec![num(1), var("j")],
+                    value: Box::new(Node::ArrayRef {
+                        ident: "board".to_string(),
+                        indices: vec![num(0), num(2)],
+                        pos: Position::new(0, 0),
+                    }),
+                    pos: Position::new(0, 0),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn global_declaration() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Global),
+            TokenKind::Ident("score".to_string()),
+            sym(SymbolKind::Equals),
+            TokenKind::Number(0),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::GlobalDecl {
+                ident: "score".to_string(),
+                value: Box::new(num(0)),
+            }])
+        );
+    }
+
+    #[test]
+    fn parameter_passing_modes() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Procedure),
+            TokenKind::Ident("swap".to_string()),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::Keyword(KeywordKind::ByRef),
+            TokenKind::Ident("a".to_string()),
+            sym(SymbolKind::Comma),
+            TokenKind::Ident("b".to_string()),
+            sym(SymbolKind::Colon),
+            TokenKind::Keyword(KeywordKind::ByVal),
+            sym(SymbolKind::Comma),
+            TokenKind::Ident("c".to_string()),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::EndProcedure),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ProcedureDecl {
+                ident: "swap".to_string(),
+                params: vec![
+                    Param {
+                        ident: "a".to_string(),
+                        passing: Passing::ByRef,
+                    },
+                    Param {
+                        ident: "b".to_string(),
+                        passing: Passing::ByVal,
+                    },
+                    Param {
+                        ident: "c".to_string(),
+                        passing: Passing::Default,
+                    },
+                ],
+                body: Box::new(Node::Block(vec![])),
+            }])
+        );
+    }
+
+    #[test]
+    fn class_declaration() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Class),
+            TokenKind::Ident("Pet".to_string()),
+            TokenKind::Keyword(KeywordKind::Private),
+            TokenKind::Ident("name".to_string()),
+            TokenKind::Ident("sound".to_string()),
+            TokenKind::Keyword(KeywordKind::Public),
+            TokenKind::Keyword(KeywordKind::Procedure),
+            TokenKind::Keyword(KeywordKind::New),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::Ident("givenName".to_string()),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Ident("name".to_string()),
+            sym(SymbolKind::Equals),
+            TokenKind::Ident("givenName".to_string()),
+            TokenKind::Keyword(KeywordKind::EndProcedure),
+            TokenKind::Keyword(KeywordKind::EndClass),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ClassDecl {
+                ident: "Pet".to_string(),
+                parent: None,
+                attributes: vec![
+                    ("name".to_string(), Visibility::Private),
+                    ("sound".to_string(), Visibility::Public),
+                ],
+                methods: vec![(
+                    Node::ProcedureDecl {
+                        ident: "new".to_string(),
+                        params: vec![Param {
+                            ident: "givenName".to_string(),
+                            passing: Passing::Default,
+                        }],
+                        body: Box::new(Node::Block(vec![Node::Assign {
+                            ident: "name".to_string(),
+                            value: Box::new(var("givenName")),
+                            pos: Position::new(0, 0),
+                        }])),
+                    },
+                    Visibility::Public,
+                )],
+            }])
+        );
+    }
+
+    #[test]
+    fn new_object_and_attribute_assign() {
+        let input = vec![
+            TokenKind::Ident("pet".to_string()),
+            sym(SymbolKind::Equals),
+            TokenKind::Keyword(KeywordKind::New),
+            TokenKind::Ident("Pet".to_string()),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::String("Fido".to_string()),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Ident("pet".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("sound".to_string()),
+            sym(SymbolKind::Equals),
+            TokenKind::String("woof".to_string()),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![
+                Node::Assign {
+                    ident: "pet".to_string(),
+                    value: Box::new(Node::New {
+                        class: "Pet".to_string(),
+                        args: vec![Node::Primary(Value::String("Fido".to_string()))],
+                        pos: Position::new(0, 0),
+                    }),
+                    pos: Position::new(0, 0),
+                },
+                Node::AttributeAssign {
+                    object: Box::new(var("pet")),
+                    ident: "sound".to_string(),
+                    value: Box::new(Node::Primary(Value::String("woof".to_string()))),
+                    pos: Position::new(0, 0),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn method_call_on_array_element() {
+        let input = vec![
+            TokenKind::Ident("pets".to_string()),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(0),
+            sym(SymbolKind::RightSqBracket),
+            sym(SymbolKind::Dot),
+            TokenKind::Ident("describe".to_string()),
+            sym(SymbolKind::LeftBracket),
+            sym(SymbolKind::RightBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::DotExpr {
+                left: Box::new(Node::ArrayRef {
+                    ident: "pets".to_string(),
+                    indices: vec![Node::Primary(Value::Number(0))],
+                    pos: Position::new(0, 0),
+                }),
+                right: "describe".to_string(),
+                args: Some(vec![]),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn constructor_called_as_method() {
+        let input = vec![
+            TokenKind::Ident("counter".to_string()),
+            sym(SymbolKind::Dot),
+            TokenKind::Keyword(KeywordKind::New),
+            sym(SymbolKind::LeftBracket),
+            sym(SymbolKind::RightBracket),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::DotExpr {
+                left: Box::new(var("counter")),
+                right: "new".to_string(),
+                args: Some(vec![]),
+                pos: Position::new(0, 0),
+            }])
+        );
+    }
+
+    #[test]
+    fn array_element_without_assignment() {
+        let input = vec![
+            TokenKind::Ident("pets".to_string()),
+            sym(SymbolKind::LeftSqBracket),
+            TokenKind::Number(0),
+            sym(SymbolKind::RightSqBracket),
+        ];
+
+        assert!(matches!(
+            Parser::parse_from_list(input),
+            Err(ParserError::IncompleteStatement(..))
+        ));
+    }
+
+    #[test]
+    fn class_inherits_and_super_call() {
+        let input = vec![
+            TokenKind::Keyword(KeywordKind::Class),
+            TokenKind::Ident("Dog".to_string()),
+            TokenKind::Keyword(KeywordKind::Inherits),
+            TokenKind::Ident("Pet".to_string()),
+            TokenKind::Keyword(KeywordKind::Procedure),
+            TokenKind::Keyword(KeywordKind::New),
+            sym(SymbolKind::LeftBracket),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::Super),
+            sym(SymbolKind::Dot),
+            TokenKind::Keyword(KeywordKind::New),
+            sym(SymbolKind::LeftBracket),
+            TokenKind::String("Fido".to_string()),
+            sym(SymbolKind::RightBracket),
+            TokenKind::Keyword(KeywordKind::EndProcedure),
+            TokenKind::Keyword(KeywordKind::EndClass),
+        ];
+
+        assert_eq!(
+            Parser::parse_from_list(input).unwrap(),
+            Node::Block(vec![Node::ClassDecl {
+                ident: "Dog".to_string(),
+                parent: Some(("Pet".to_string(), Position::new(0, 0))),
+                attributes: vec![],
+                methods: vec![(
+                    Node::ProcedureDecl {
+                        ident: "new".to_string(),
+                        params: vec![],
+                        body: Box::new(Node::Block(vec![Node::SuperCall {
+                            method: "new".to_string(),
+                            args: vec![Node::Primary(Value::String("Fido".to_string()))],
+                            pos: Position::new(0, 0),
+                        }])),
+                    },
+                    Visibility::Public,
+                )],
+            }])
+        );
+    }
 }
@@ -1,24 +1,205 @@
 use std::collections::HashMap;
 
-use crate::Value;
+use crate::{object::ObjectRef, Num, Position, Value};
 
+/// Where a variable's value is stored
+#[derive(Clone, Debug)]
+pub enum Location {
+    /// A variable in the frame at the given depth
+    Local(usize, String),
+    /// An attribute of an object, used by name inside one of its methods
+    Attribute(ObjectRef, String),
+    Global(String),
+}
+
+/// A byRef parameter, which reads and writes the caller's variable, or one of
+/// its elements when `indices` isn't empty. `ident` and `pos` are the caller's
+/// variable name and argument position, used for errors when indexing.
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub location: Location,
+    pub ident: String,
+    pub indices: Vec<Num>,
+    pub pos: Position,
+}
+
+/// A variable in a frame, which either holds its own value or refers to another
+#[derive(Clone, Debug)]
+pub enum Variable {
+    Value(Value),
+    Reference(Reference),
+}
+
+/// The variables of the main program or a single subroutine call
+#[derive(Debug, Default)]
+struct Frame {
+    variables: HashMap<String, Variable>,
+    /// The object a method was called on, whose attributes can be used by name
+    object: Option<ObjectRef>,
+    /// The class the running method was declared in
+    class: Option<String>,
+}
+
+/// Stores variables following OCR's scoping rules. Variables declared with
+/// `global` live in a single global frame that is visible everywhere. Every
+/// other variable is local to the frame it was first assigned in: the bottom
+/// frame belongs to the main program, and every subroutine call pushes a new
+/// frame on top of it. Inside a method, the object's attributes are visible
+/// between the local variables and the globals.
 #[derive(Debug)]
 pub struct SymbolTable {
-    symbols: HashMap<String, Value>,
+    globals: HashMap<String, Value>,
+    frames: Vec<Frame>,
 }
 
 impl SymbolTable {
     pub fn new() -> Self {
         Self {
-            symbols: HashMap::new(),
+            globals: HashMap::new(),
+            frames: vec![Frame::default()],
         }
     }
 
+    pub fn push_scope(&mut self) {
+        self.frames.push(Frame::default());
+    }
+
+    /// Pushes the frame for a method call on `object`, where the method was
+    /// declared in `class`
+    pub fn push_method_scope(&mut self, object: ObjectRef, class: String) {
+        self.frames.push(Frame {
+            variables: HashMap::new(),
+            object: Some(object),
+            class: Some(class),
+        });
+    }
+
+    pub fn pop_scope(&mut self) {
+        if self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+
+    /// The object and declaring class of the running method, if any
+    pub fn current_method(&self) -> Option<(ObjectRef, String)> {
+        let frame = self.frames.last().unwrap();
+        match (&frame.object, &frame.class) {
+            (Some(object), Some(class)) => Some((object.clone(), class.clone())),
+            _ => None,
+        }
+    }
+
+    /// Declares a global variable, replacing any local variable of the same name
+    /// in the current frame so it can't shadow the global
+    pub fn declare_global(&mut self, ident: String, value: Value) {
+        self.current_frame().variables.remove(&ident);
+        self.globals.insert(ident, value);
+    }
+
+    /// Declares a variable in the current frame, even if a global of the same
+    /// name exists. Used for parameters and arrays, which always shadow globals.
+    pub fn declare_local(&mut self, ident: String, variable: Variable) {
+        self.current_frame().variables.insert(ident, variable);
+    }
+
+    /// Assigns to a local variable if one exists, then to an attribute of the
+    /// current object, then to a global variable, and otherwise creates a new
+    /// local variable in the current frame. References are replaced rather than
+    /// written through, so callers should check for them with `get_variable` first.
     pub fn assign_variable(&mut self, ident: String, value: Value) {
-        self.symbols.insert(ident, value);
+        // PANIC: there is always at least the main program frame
+        let frame = self.frames.last_mut().unwrap();
+        if !frame.variables.contains_key(&ident) {
+            if let Some(object) = &frame.object {
+                if let Some(attribute) = object.borrow_mut().attributes.get_mut(&ident) {
+                    *attribute = value;
+                    return;
+                }
+            }
+            if let Some(global) = self.globals.get_mut(&ident) {
+                *global = value;
+                return;
+            }
+        }
+        frame.variables.insert(ident, Variable::Value(value));
+    }
+
+    /// Looks up a variable in the current frame, then the current object's
+    /// attributes, then the globals. Variables local to callers or the main
+    /// program are not visible, so `None` is returned for them.
+    pub fn get_variable(&self, ident: String) -> Option<Variable> {
+        // PANIC: there is always at least the main program frame
+        let frame = self.frames.last().unwrap();
+        if let Some(x) = frame.variables.get(&ident) {
+            return Some(x.clone());
+        }
+        if let Some(object) = &frame.object {
+            if let Some(x) = object.borrow().attributes.get(&ident) {
+                return Some(Variable::Value(x.clone()));
+            }
+        }
+        self.globals.get(&ident).cloned().map(Variable::Value)
+    }
+
+    /// Finds where a visible variable is stored, following the same lookup
+    /// order as `get_variable`
+    pub fn locate(&self, ident: String) -> Option<Location> {
+        let depth = self.frames.len() - 1;
+        let frame = &self.frames[depth];
+        if frame.variables.contains_key(&ident) {
+            return Some(Location::Local(depth, ident));
+        }
+        if let Some(object) = &frame.object {
+            if object.borrow().attributes.contains_key(&ident) {
+                return Some(Location::Attribute(object.clone(), ident));
+            }
+        }
+        if self.globals.contains_key(&ident) {
+            return Some(Location::Global(ident));
+        }
+        None
+    }
+
+    /// Reads the value stored at a location
+    pub fn read(&self, location: &Location) -> Option<Value> {
+        match location {
+            Location::Local(depth, ident) => match self.frames.get(*depth)?.variables.get(ident)? {
+                Variable::Value(x) => Some(x.clone()),
+                // references are always made to the variable they refer to
+                Variable::Reference(_) => None,
+            },
+            Location::Attribute(object, ident) => object.borrow().attributes.get(ident).cloned(),
+            Location::Global(ident) => self.globals.get(ident).cloned(),
+        }
+    }
+
+    /// Replaces the value stored at a location
+    pub fn write(&mut self, location: &Location, value: Value) {
+        match location {
+            Location::Local(depth, ident) => {
+                if let Some(frame) = self.frames.get_mut(*depth) {
+                    frame
+                        .variables
+                        .insert(ident.clone(), Variable::Value(value));
+                }
+            }
+            Location::Attribute(object, ident) => {
+                object.borrow_mut().attributes.insert(ident.clone(), value);
+            }
+            Location::Global(ident) => {
+                self.globals.insert(ident.clone(), value);
+            }
+        }
     }
 
-    pub fn get_variable(&mut self, ident: String) -> Value {
-        self.symbols.get(&ident).unwrap().clone()
+    fn current_frame(&mut self) -> &mut Frame {
+        // PANIC: there is always at least the main program frame
+        self.frames.last_mut().unwrap()
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
     }
 }